@@ -1,15 +1,16 @@
+use crate::cache::*;
 use crate::utils::*;
 use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 /// This generates "tflite_types.rs" containing structs and enums which are
-/// inter-operable with rust
-pub fn bindgen_tflite_types(tensorflow_location: &Path) {
+/// inter-operable with rust, unless it's cached with `cache_key`
+pub fn bindgen_tflite_types(tensorflow_location: &Path, cache_key: &str) {
     use bindgen::*;
 
     let tflite_types_name = OUT_DIR.join("tflite_types.rs");
 
-    if !tflite_types_name.exists() || cfg!(feature = "build") {
+    if !is_cached(&tflite_types_name, cache_key) {
         println!("Running bindgen...");
 
         let start = Instant::now();
@@ -19,7 +20,12 @@ pub fn bindgen_tflite_types(tensorflow_location: &Path) {
             .allowlist_recursively(true) // Allow types under types.
             .prepend_enum_name(false) // Don't preprend the enum name to variants -- it's ugly!
             .impl_debug(true) // Let us debug the types.
-            .with_codegen_config(CodegenConfig::TYPES) // TODO: figure out what this does.
+            // Types, plus the functions and constants of TFLM's C API.
+            // C++ methods and constructors aren't generated, and are called
+            // through `cpp!` instead.
+            .with_codegen_config(
+                CodegenConfig::TYPES | CodegenConfig::FUNCTIONS | CodegenConfig::VARS,
+            )
             .layout_tests(false)
             .enable_cxx_namespaces()
             .derive_copy(true)
@@ -41,12 +47,28 @@ pub fn bindgen_tflite_types(tensorflow_location: &Path) {
             .opaque_type("tflite::Model")
             .allowlist_type("tflite::MicroInterpreter")
             .opaque_type("tflite::MicroInterpreter")
-            .allowlist_type("tflite::ops::micro::AllOpsResolver")
-            .opaque_type("tflite::ops::micro::AllOpsResolver")
             .allowlist_type("TfLiteTensor")
-            .allowlist_type("FrontendState")
-            .allowlist_type("FrontendConfig")
-            .allowlist_type("FrontendOutput")
+            .allowlist_type("TfLiteEvalTensor")
+            .allowlist_type("TfLiteStatus")
+            .allowlist_type("TfLiteType")
+            .allowlist_type("TfLiteQuantization")
+            .allowlist_type("TfLiteAffineQuantization")
+            .allowlist_type("TfLiteIntArray")
+            .allowlist_type("TfLiteFloatArray")
+            .allowlist_type("TfLiteNode")
+            .allowlist_type("TfLiteContext")
+            .allowlist_type("TFLMRegistration")
+            .allowlist_type("TfLiteFusedActivation")
+            .allowlist_type("TfLitePadding")
+            .allowlist_type("TfLite.*Params") // Builtin operator options
+            // Functions - TFLM's C API
+            .allowlist_function("TfLiteIntArray.*")
+            .allowlist_function("TfLiteFloatArray.*")
+            .allowlist_function("TfLiteTypeGetName")
+            // bindgen can't instantiate templates with non-type parameters
+            // itself, so the op resolver is instantiated through a typedef.
+            .allowlist_type("tflite_micro_rs::OpResolver")
+            .opaque_type("tflite_micro_rs::OpResolver")
             // Types - blocklist
             .blocklist_type("std")
             .blocklist_type("tflite::Interpreter_TfLiteDelegatePtr")
@@ -68,6 +90,21 @@ pub fn bindgen_tflite_types(tensorflow_location: &Path) {
                 "{}/tensorflow/lite/micro/micro_interpreter.h",
                 tensorflow_location.to_string_lossy()
             ))
+            .header(format!(
+                "{}/tensorflow/lite/c/common.h",
+                tensorflow_location.to_string_lossy()
+            ))
+            .header(format!(
+                "{}/tensorflow/lite/c/builtin_op_data.h",
+                tensorflow_location.to_string_lossy()
+            ))
+            .header_contents(
+                "tflite_micro_rs.h",
+                "#include \"tensorflow/lite/micro/micro_mutable_op_resolver.h\"\n\
+                 namespace tflite_micro_rs {\n\
+                     typedef tflite::MicroMutableOpResolver<128> OpResolver;\n\
+                 }",
+            )
             // Inclusions
             .clang_arg(format!(
                 "-include{}/tensorflow/lite/micro/micro_common.h",
@@ -100,6 +137,53 @@ pub fn bindgen_tflite_types(tensorflow_location: &Path) {
             .clang_arg("-xc++")
             .clang_arg("-std=c++17"); // C++17 is required for flatbuffers
 
+        // Defines that change struct layouts must match the C++ build, see
+        // `abi_defines`. The others are those of `tensorflow_build_setup`
+        // that change what the headers declare.
+        let bindings = abi_defines().iter().fold(bindings, |bindings, define| {
+            bindings.clang_arg(format!("-D{}", define))
+        });
+        let bindings = if cfg!(feature = "minimal") {
+            bindings.clang_arg("-DTF_LITE_STRIP_ERROR_STRINGS")
+        } else {
+            bindings
+        };
+
+        // The user's defines may also change struct layouts.
+        let bindings = TFLM_DEFINES
+            .iter()
+            .fold(bindings, |bindings, define| match define {
+                (name, Some(value)) => bindings.clang_arg(format!("-D{}={}", name, value)),
+                (name, None) => bindings.clang_arg(format!("-D{}", name)),
+            });
+
+        // Audio frontend types
+        let bindings = if cfg!(feature = "frontend") {
+            bindings
+                .allowlist_type("FrontendState")
+                .allowlist_type("FrontendConfig")
+                .allowlist_type("FrontendOutput")
+                .header(format!(
+                    "{}/tensorflow/lite/experimental/microfrontend/lib/frontend_util.h",
+                    tensorflow_location.to_string_lossy()
+                ))
+        } else {
+            bindings
+        };
+
+        // Profiler types
+        let bindings = if cfg!(feature = "profiler") {
+            bindings
+                .allowlist_type("tflite::MicroProfiler")
+                .opaque_type("tflite::MicroProfiler")
+                .header(format!(
+                    "{}/tensorflow/lite/micro/micro_profiler.h",
+                    tensorflow_location.to_string_lossy()
+                ))
+        } else {
+            bindings
+        };
+
         // Dump the preprocessed input for debugging. Stored in __bindgen.* files.
         bindings
             .dump_preprocessed_input()
@@ -112,6 +196,7 @@ pub fn bindgen_tflite_types(tensorflow_location: &Path) {
         bindings
             .write_to_file(out_path)
             .expect("Couldn't write bindings!");
+        store_key(&tflite_types_name, cache_key);
 
         println!("Running bindgen took {:?}", start.elapsed());
     } else {
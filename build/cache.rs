@@ -0,0 +1,220 @@
+use crate::utils::*;
+use std::env;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+
+/// The file, within a tree generated by this build, recording which TFLM
+/// revision it was generated from.
+pub const REVISION_STAMP: &str = ".tflm-revision";
+
+/// Environment variables read by `cc` that change the compiled output.
+/// Each is also read with the target as a suffix, and with a `TARGET_` or
+/// `HOST_` prefix.
+const COMPILER_ENV_VARS: &[&str] = &[
+    "CC",
+    "CXX",
+    "AR",
+    "CFLAGS",
+    "CXXFLAGS",
+    "CRATE_CC_NO_DEFAULTS",
+];
+
+/// Tell cargo when to rerun the build script. The build script reruns when
+/// its own sources change, when a source in `src` with inline C++ changes,
+/// and when any of the inputs to [`cache_key`] change. Other Rust sources
+/// don't affect its output.
+pub fn emit_rerun_directives() {
+    println!("cargo:rerun-if-changed=build");
+
+    // `cpp_build` doesn't emit these itself
+    for source in inline_cpp_sources(Path::new("src")) {
+        println!("cargo:rerun-if-changed={}", source.display());
+    }
+}
+
+/// The Rust sources under `dir` that contain inline C++, or declare
+/// modules that `cpp_build` follows to find it.
+fn inline_cpp_sources(dir: &Path) -> Vec<PathBuf> {
+    let mut sources = vec![];
+    for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            sources.extend(inline_cpp_sources(&path));
+        } else if path.extension().map_or(false, |e| e == "rs")
+            && fs::read_to_string(&path).map_or(true, |source| {
+                source.contains("cpp!") || source.contains("mod ")
+            })
+        {
+            sources.push(path);
+        }
+    }
+    sources
+}
+
+/// 64-bit FNV-1a. Unlike `DefaultHasher`, its output is specified, so a
+/// cache key stays the same across Rust releases.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// A key identifying the compiled library and bindings. They are rebuilt
+/// whenever the key changes, which it does with the target, the compiler
+/// and its flags, the enabled features, the build script and the TFLM
+/// source.
+pub fn cache_key(tensorflow_location: &Path) -> String {
+    let mut hasher = Fnv1a::new();
+
+    TARGET.as_str().hash(&mut hasher);
+    HOST.as_str().hash(&mut hasher);
+
+    for var in ["OPT_LEVEL", "DEBUG", "PROFILE"] {
+        env::var_os(var).hash(&mut hasher);
+    }
+    for var in COMPILER_ENV_VARS {
+        for name in compiler_env_var_names(var) {
+            println!("cargo:rerun-if-env-changed={}", name);
+            env::var_os(&name).hash(&mut hasher);
+        }
+    }
+
+    let mut features: Vec<String> = env::vars()
+        .map(|(name, _)| name)
+        .filter(|name| name.starts_with("CARGO_FEATURE_"))
+        .collect();
+    features.sort();
+    features.hash(&mut hasher);
+
+    optimized_kernel_dir().hash(&mut hasher);
+    TFLM_CFLAGS.hash(&mut hasher);
+    TFLM_DEFINES.hash(&mut hasher);
+
+    // The build script sets the compile flags itself
+    let mut build_sources = get_files_glob(PathBuf::from("build/*.rs"));
+    build_sources.sort();
+    for source in build_sources {
+        fs::read(&source)
+            .expect("Unable to read build script source")
+            .hash(&mut hasher);
+    }
+
+    tensorflow_location.hash(&mut hasher);
+    source_fingerprint(tensorflow_location).hash(&mut hasher);
+
+    format!("{:016x}", hasher.finish())
+}
+
+/// Returns whether `artifact` exists and was built with `key`.
+pub fn is_cached(artifact: &Path, key: &str) -> bool {
+    if cfg!(feature = "build") || !artifact.exists() {
+        return false;
+    }
+
+    fs::read_to_string(key_file(artifact)).map_or(false, |cached| cached == key)
+}
+
+/// Record that `artifact` was built with `key`.
+pub fn store_key(artifact: &Path, key: &str) {
+    fs::write(key_file(artifact), key).expect("Unable to write cache key");
+}
+
+fn key_file(artifact: &Path) -> PathBuf {
+    let mut name = artifact.file_name().unwrap().to_owned();
+    name.push(".cache-key");
+    artifact.with_file_name(name)
+}
+
+/// The revision of the TFLM git submodule, or `None` if it isn't a git
+/// checkout or git isn't available. Also tells cargo to rerun the build
+/// script when the checked out commit changes.
+pub fn source_revision() -> Option<String> {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .current_dir(TENSORFLOW_LOCATION.as_path())
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| String::from_utf8(output.stdout).ok())
+            .map(|output| output.trim().to_string())
+    };
+
+    if let Some(head) = git(&["rev-parse", "--git-path", "HEAD"]) {
+        let head = TENSORFLOW_LOCATION.join(head);
+        println!("cargo:rerun-if-changed={}", head.display());
+    }
+
+    git(&["rev-parse", "HEAD"])
+}
+
+/// Identifies the TFLM source in `tensorflow_location`. Trees generated by
+/// this build record the revision they came from. Trees from elsewhere are
+/// identified by the newest modification time of their sources.
+fn source_fingerprint(tensorflow_location: &Path) -> String {
+    if let Ok(revision) = fs::read_to_string(tensorflow_location.join(REVISION_STAMP)) {
+        return revision;
+    }
+
+    let mut newest = SystemTime::UNIX_EPOCH;
+    for dir in ["tensorflow", "third_party"] {
+        let dir = tensorflow_location.join(dir);
+        println!("cargo:rerun-if-changed={}", dir.display());
+        newest = newest.max(newest_modification(&dir));
+    }
+
+    format!("{:?}", newest)
+}
+
+fn newest_modification(path: &Path) -> SystemTime {
+    let metadata = match fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(_) => return SystemTime::UNIX_EPOCH,
+    };
+    let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+
+    if !metadata.is_dir() {
+        return modified;
+    }
+
+    fs::read_dir(path)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| newest_modification(&entry.path()))
+        .fold(modified, SystemTime::max)
+}
+
+/// The names `cc` reads the variable `var` from, for the current target.
+fn compiler_env_var_names(var: &str) -> Vec<String> {
+    let kind = if is_cross_compiling() {
+        "TARGET"
+    } else {
+        "HOST"
+    };
+
+    vec![
+        format!("{}_{}", var, TARGET.as_str()),
+        format!("{}_{}", var, TARGET.replace('-', "_")),
+        format!("{}_{}", kind, var),
+        var.to_string(),
+    ]
+}
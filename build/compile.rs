@@ -1,5 +1,9 @@
+use crate::cache::*;
+use crate::kernels::*;
 use crate::utils::*;
+use std::env;
 use std::path::{Path, PathBuf};
+use std::process::Command;
 use std::time::Instant;
 
 /// Links the `libm` library. This is necessary because the `tflite-micro` library
@@ -53,21 +57,19 @@ pub fn build_inline_cpp(tensorflow_location: &Path) {
     println!("Building inline C++ took {:?}", start.elapsed());
 }
 
-/// Build the tensorflow library.
-pub fn build_tflm(tensorflow_location: &Path) {
+/// Build the tensorflow library, unless it's cached with `cache_key`.
+pub fn build_tflm(tensorflow_location: &Path, cache_key: &str) {
     // The path to the tensorflow directory in the source directory.
     let tflite: PathBuf = tensorflow_location.join("tensorflow");
     // The path to the third party directory where helper libraries exist.
     let tf_third_party_dir = tensorflow_location.join("third_party");
-    // The name of the final library to link.
-    let tflm_lib_name = OUT_DIR.join("tflm.a");
+    // The final library to link.
+    let tflm_lib = OUT_DIR.join("libtflm.a");
 
-    // If we don't have the library, or we're building it, build and bind TFLM.
-    if !tflm_lib_name.exists() || cfg!(feature = "build") {
+    // If we don't have the library, it's stale, or we're building it, build TFLM.
+    if !is_cached(&tflm_lib, cache_key) {
         println!("Building TFLM...");
 
-        // TODO: See below!
-        // let target: String = env::var("TARGET").unwrap_or_else(|_| "".to_string());
         let start = Instant::now();
 
         let mut builder = cc::Build::new();
@@ -76,6 +78,7 @@ pub fn build_tflm(tensorflow_location: &Path) {
             .cpp(true) // We're building with C++.
             .tensorflow_build_setup()
             .cpp_link_stdlib(None)
+            .cargo_metadata(false) // Linked below, whether or not it's rebuilt.
             // Include helper libraries.
             .include(tensorflow_location)
             .include(&tf_third_party_dir)
@@ -83,14 +86,33 @@ pub fn build_tflm(tensorflow_location: &Path) {
             .include(tf_third_party_dir.join("flatbuffers/include"))
             .include(tf_third_party_dir.join("ruy"))
             // Compile core TFLM files.
-            .files(get_files_glob(tflite.join("lite/micro/*.cc")))
-            .files(get_files_glob(tflite.join("lite/micro/kernels/*.cc")))
+            .files(
+                get_files_glob(tflite.join("lite/micro/*.cc"))
+                    .into_iter()
+                    // The profiler is only compiled if it's asked for.
+                    .filter(|p| cfg!(feature = "profiler") || !p.contains("micro_profiler.cc")),
+            )
+            .files(
+                kernel_sources(&tflite.join("lite/micro/kernels"))
+                    .into_iter()
+                    // Only the operators asked for are compiled.
+                    .filter(|p| is_kernel_selected(p)),
+            )
             .files(get_files_glob(
                 tflite.join("lite/micro/memory_planner/*.cc"),
             ))
             .files(get_files_glob(
-                tflite.join("lite/experimental/microfrontend/lib/*.c"),
+                tflite.join("lite/micro/arena_allocator/*.cc"),
             ))
+            .files(
+                get_files_glob(tflite.join("lite/micro/tflite_bridge/*.cc"))
+                    .into_iter()
+                    // With `minimal`, the error reporter is a no-op defined
+                    // in src/interop.rs.
+                    .filter(|p| {
+                        !cfg!(feature = "minimal") || !p.contains("micro_error_reporter.cc")
+                    }),
+            )
             .file(tflite.join("lite/core/c/common.cc"))
             .file(tflite.join("lite/core/api/error_reporter.cc"))
             .file(tflite.join("lite/core/api/flatbuffer_conversions.cc"))
@@ -99,39 +121,176 @@ pub fn build_tflm(tensorflow_location: &Path) {
             .file(tflite.join("lite/kernels/internal/quantization_util.cc"))
             .file(tflite.join("lite/kernels/kernel_util.cc"));
 
-        // TODO: Add this back!
-        // // CMSIS-NN for ARM Cortex-M targets
-        // if target.starts_with("thumb") && target.contains("m-none-") && cfg!(feature = "cmsis-nn") {
-        //     println!("Build includes CMSIS-NN.");
-        //     let cmsis = tflite.join("lite/micro/tools/make/downloads/cmsis");
-
-        //     builder_ref
-        //         .files(get_files_glob(cmsis.join("CMSIS/NN/Source/*.c")))
-        //         .include(cmsis.join("CMSIS/NN/Include"))
-        //         .include(cmsis.join("CMSIS/DSP/Include"))
-        //         .include(cmsis.join("CMSIS/Core/Include"));
-        // }
-
-        // Micro frontend.
-        builder_ref
-            .include(tf_third_party_dir.join("kissfft"))
-            .include(tf_third_party_dir.join("kissfft/tools"))
-            .include(tflite.join("lite/experimental/microfrontend/lib"))
-            .file(tf_third_party_dir.join("kissfft/kiss_fft.c"))
-            .file(tf_third_party_dir.join("kissfft/tools/kiss_fftr.c"))
-            .files(get_files_glob(
-                tflite.join("lite/experimental/microfrontend/lib/*.cc"),
-            ));
+        // Third party libraries used by the optimized kernels.
+        match optimized_kernel_dir() {
+            Some("cmsis_nn") => {
+                println!("Build includes CMSIS-NN.");
+                let cmsis_nn = tf_third_party_dir.join("cmsis_nn");
+
+                builder_ref
+                    .files(get_files_glob(cmsis_nn.join("Source/*/*.c")))
+                    .include(&cmsis_nn)
+                    .include(cmsis_nn.join("Include"));
+            }
+            Some("ethos_u") => {
+                println!("Build includes the Ethos-U core driver.");
+                let driver = tf_third_party_dir.join("ethos_u_core_driver");
+
+                builder_ref
+                    .files(get_files_glob(driver.join("src/*.c")))
+                    .include(driver.join("include"));
+            }
+            // The Xtensa, CEVA and ARC MLI libraries are built with the
+            // vendor's toolchain, and must be linked by the user.
+            _ => {}
+        }
 
         // Compile!
         builder_ref.compile("tflm");
+        store_key(&tflm_lib, cache_key);
 
         println!("Building TFLM from source took {:?}", start.elapsed());
     } else {
-        println!("Not rebuilding TFLM, using {:?}", tflm_lib_name);
+        println!("Not rebuilding TFLM, using {:?}", tflm_lib);
+    }
+
+    // Micro frontend. This is compiled separately so that its calls to
+    // `malloc` and `free` can be redirected to the allocator in
+    // src/frontend/memory.rs, which can use a caller-provided buffer.
+    if cfg!(feature = "frontend") {
+        build_frontend(tensorflow_location, cache_key);
+    }
+
+    // Tell cargo to link the tflm library.
+    println!("cargo:rustc-link-search=native={}", OUT_DIR.display());
+    println!("cargo:rustc-link-lib=static=tflm");
+}
+
+/// Returns the kernel sources to compile from `kernels`. If an optimized
+/// kernel directory is selected, its kernels replace the reference kernels
+/// of the same name, as TFLM's Makefile does.
+fn kernel_sources(kernels: &Path) -> Vec<String> {
+    let reference = get_files_glob(kernels.join("*.cc"));
+
+    let optimized_kernel_dir = match optimized_kernel_dir() {
+        Some(dir) => dir,
+        None => return reference,
+    };
+
+    println!("Using optimized kernels from {}", optimized_kernel_dir);
+    let optimized_dir = kernels.join(optimized_kernel_dir);
+    if !optimized_dir.is_dir() {
+        panic!(
+            "Optimized kernel directory {} does not exist",
+            optimized_dir.display()
+        );
+    }
+    let optimized = get_files_glob(optimized_dir.join("*.cc"));
+
+    let file_name = |p: &String| Path::new(p).file_name().map(|n| n.to_owned());
+    let optimized_names: Vec<_> = optimized.iter().map(file_name).collect();
+
+    reference
+        .into_iter()
+        .filter(|p| !optimized_names.contains(&file_name(p)))
+        .chain(optimized)
+        .collect()
+}
+
+/// Build the audio frontend library, unless it's cached with `cache_key`.
+pub fn build_frontend(tensorflow_location: &Path, cache_key: &str) {
+    let tflite: PathBuf = tensorflow_location.join("tensorflow");
+    let tf_third_party_dir = tensorflow_location.join("third_party");
+    let frontend_lib = OUT_DIR.join("libtflm_frontend.a");
+
+    if is_cached(&frontend_lib, cache_key) {
+        println!("Not rebuilding frontend, using {:?}", frontend_lib);
+    } else {
+        compile_frontend(tensorflow_location, &tflite, &tf_third_party_dir);
+        store_key(&frontend_lib, cache_key);
+    }
+
+    println!("cargo:rustc-link-search=native={}", OUT_DIR.display());
+    println!("cargo:rustc-link-lib=static=tflm_frontend");
+}
+
+fn compile_frontend(tensorflow_location: &Path, tflite: &Path, tf_third_party_dir: &Path) {
+    let mut build = cc::Build::new();
+    build
+        .cpp(true)
+        .tensorflow_build_setup()
+        .cpp_link_stdlib(None)
+        .cargo_metadata(false)
+        // Keep calls to `malloc` and `free` as they are, eg. not merged
+        // with a `memset` into `calloc`, so that they can be renamed below
+        .flag_if_supported("-fno-builtin-malloc")
+        .flag_if_supported("-fno-builtin-calloc")
+        .flag_if_supported("-fno-builtin-free")
+        .include(tensorflow_location)
+        .include(tf_third_party_dir)
+        .include(tf_third_party_dir.join("kissfft"))
+        .include(tf_third_party_dir.join("kissfft/tools"))
+        .include(tflite.join("lite/experimental/microfrontend/lib"))
+        .file(tf_third_party_dir.join("kissfft/kiss_fft.c"))
+        .file(tf_third_party_dir.join("kissfft/tools/kiss_fftr.c"))
+        .files(get_files_glob(
+            tflite.join("lite/experimental/microfrontend/lib/*.c"),
+        ))
+        .files(get_files_glob(
+            tflite.join("lite/experimental/microfrontend/lib/*.cc"),
+        ))
+        .compile("tflm_frontend");
+
+    // Allocate the frontend state with `tflm_frontend_malloc` and
+    // `tflm_frontend_free` from src/frontend/memory.rs, rather than the C
+    // library's heap. Only the library's references are renamed, not the
+    // declarations in system headers.
+    run_command_or_fail(
+        OUT_DIR.as_path(),
+        objcopy(&build),
+        &[
+            "--redefine-sym",
+            "malloc=tflm_frontend_malloc",
+            "--redefine-sym",
+            "free=tflm_frontend_free",
+            "libtflm_frontend.a",
+        ],
+    );
+}
+
+/// The `objcopy` for the target, from the `OBJCOPY` environment variable,
+/// or else the first that runs of: the one alongside the archiver, eg.
+/// `arm-none-eabi-objcopy` alongside `arm-none-eabi-ar`, then
+/// `llvm-objcopy` and `objcopy` on the `PATH`.
+fn objcopy(build: &cc::Build) -> PathBuf {
+    println!("cargo:rerun-if-env-changed=OBJCOPY");
+    if let Some(objcopy) = env::var_os("OBJCOPY").filter(|o| !o.is_empty()) {
+        return PathBuf::from(objcopy);
+    }
+
+    let archiver = PathBuf::from(build.get_archiver().get_program());
+    let name = archiver.file_name().unwrap_or_default().to_string_lossy();
+    let alongside_archiver = name
+        .strip_suffix("ar")
+        .map(|prefix| archiver.with_file_name(format!("{}objcopy", prefix)));
+
+    let candidates: Vec<PathBuf> = alongside_archiver
+        .into_iter()
+        .chain(["llvm-objcopy", "objcopy"].map(PathBuf::from))
+        .collect();
+
+    let runs = |objcopy: &&PathBuf| {
+        Command::new(objcopy)
+            .arg("--version")
+            .output()
+            .map_or(false, |output| output.status.success())
+    };
 
-        // Tell cargo to link the tflm library.
-        println!("cargo:rustc-link-lib=static=tflm");
-        println!("cargo:rustc-link-search=native={}", OUT_DIR.display());
+    match candidates.iter().find(runs) {
+        Some(objcopy) => objcopy.clone(),
+        None => panic!(
+            "No objcopy found for the `frontend` feature, tried {:?}. Set OBJCOPY to the objcopy for the target.",
+            candidates
+        ),
     }
 }
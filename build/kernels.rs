@@ -0,0 +1,117 @@
+use crate::utils::optimized_kernel_dir;
+use std::path::Path;
+
+/// The kernel sources implementing the operators bound in src/operators,
+/// and whether the `op-*` feature selecting each is enabled. These match
+/// the modules of src/operators.
+const OPERATOR_KERNELS: &[(bool, &[&str])] = &[
+    (
+        cfg!(feature = "op-activations"),
+        &["activations.cc", "activations_common.cc"],
+    ),
+    (cfg!(feature = "op-add"), &["add.cc", "add_common.cc"]),
+    (cfg!(feature = "op-arg-min-max"), &["arg_min_max.cc"]),
+    (cfg!(feature = "op-ceil"), &["ceil.cc"]),
+    (cfg!(feature = "op-comparisons"), &["comparisons.cc"]),
+    (cfg!(feature = "op-concatenation"), &["concatenation.cc"]),
+    (cfg!(feature = "op-conv"), &["conv.cc", "conv_common.cc"]),
+    (
+        cfg!(feature = "op-depthwise-conv"),
+        &["depthwise_conv.cc", "depthwise_conv_common.cc"],
+    ),
+    (
+        cfg!(feature = "op-dequantize"),
+        &["dequantize.cc", "dequantize_common.cc"],
+    ),
+    (cfg!(feature = "op-elementwise"), &["elementwise.cc"]),
+    (cfg!(feature = "op-floor"), &["floor.cc"]),
+    (
+        cfg!(feature = "op-fully-connected"),
+        &["fully_connected.cc", "fully_connected_common.cc"],
+    ),
+    (cfg!(feature = "op-l2-normalization"), &["l2norm.cc"]),
+    (
+        cfg!(feature = "op-logical"),
+        &["logical.cc", "logical_common.cc"],
+    ),
+    (
+        cfg!(feature = "op-logistic"),
+        &["logistic.cc", "logistic_common.cc"],
+    ),
+    (
+        cfg!(feature = "op-maximum-minimum"),
+        &["maximum_minimum.cc"],
+    ),
+    (cfg!(feature = "op-mul"), &["mul.cc", "mul_common.cc"]),
+    (cfg!(feature = "op-neg"), &["neg.cc"]),
+    (cfg!(feature = "op-pack"), &["pack.cc"]),
+    (cfg!(feature = "op-pad"), &["pad.cc"]),
+    (
+        cfg!(feature = "op-pooling"),
+        &["pooling.cc", "pooling_common.cc"],
+    ),
+    (cfg!(feature = "op-prelu"), &["prelu.cc", "prelu_common.cc"]),
+    (
+        cfg!(feature = "op-quantize"),
+        &["quantize.cc", "quantize_common.cc"],
+    ),
+    (
+        cfg!(feature = "op-reduce"),
+        &["reduce.cc", "reduce_common.cc"],
+    ),
+    (
+        cfg!(feature = "op-reshape"),
+        &["reshape.cc", "reshape_common.cc"],
+    ),
+    (
+        cfg!(feature = "op-resize-nearest-neighbor"),
+        &["resize_nearest_neighbor.cc"],
+    ),
+    (cfg!(feature = "op-round"), &["round.cc"]),
+    (
+        cfg!(feature = "op-softmax"),
+        &["softmax.cc", "softmax_common.cc"],
+    ),
+    (cfg!(feature = "op-split"), &["split.cc"]),
+    (
+        cfg!(feature = "op-strided-slice"),
+        &["strided_slice.cc", "strided_slice_common.cc"],
+    ),
+    (cfg!(feature = "op-sub"), &["sub.cc", "sub_common.cc"]),
+    (cfg!(feature = "op-svdf"), &["svdf.cc", "svdf_common.cc"]),
+    (cfg!(feature = "op-tanh"), &["tanh.cc"]),
+    (cfg!(feature = "op-unpack"), &["unpack.cc"]),
+];
+
+/// Kernel sources shared between operators, which are compiled whichever
+/// operators are selected.
+const SHARED_KERNEL_SOURCES: &[&str] = &["kernel_util.cc", "micro_tensor_utils.cc"];
+
+/// Returns whether the kernel source `path` should be compiled: the shared
+/// sources, and the kernels of the operators whose `op-*` feature is
+/// enabled. The kernels of other operators are left out, as they can't be
+/// added to a resolver. Sources in the optimized kernel directory that
+/// aren't an operator's kernel, such as a vendor's helpers, are always
+/// compiled.
+pub fn is_kernel_selected(path: &str) -> bool {
+    let path = Path::new(path);
+    let file_name = match path.file_name().and_then(|n| n.to_str()) {
+        Some(file_name) => file_name,
+        None => return true,
+    };
+
+    if let Some((enabled, _)) = OPERATOR_KERNELS
+        .iter()
+        .find(|(_, files)| files.contains(&file_name))
+    {
+        return *enabled;
+    }
+
+    let in_optimized_dir = path
+        .parent()
+        .and_then(|dir| dir.file_name())
+        .and_then(|dir| dir.to_str())
+        .map_or(false, |dir| Some(dir) == optimized_kernel_dir());
+
+    SHARED_KERNEL_SOURCES.contains(&file_name) || in_optimized_dir
+}
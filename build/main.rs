@@ -1,25 +1,43 @@
 mod bind;
+mod cache;
 mod compile;
+mod kernels;
+mod prebuilt;
 mod prep_src;
 mod utils;
 
 use bind::*;
+use cache::*;
 use compile::*;
+use prebuilt::*;
 use prep_src::*;
 
 /// The buildscript that builds the C++ code. Yay!
 fn main() {
-    let tensorflow_location = prepare_tensorflow_source();
+    emit_rerun_directives();
+
+    // A prebuilt TFLM only needs its headers, for the bindings and inline C++.
+    let prebuilt = find_prebuilt_tflm();
+    let tensorflow_location = match &prebuilt {
+        Some(prebuilt) => prebuilt.include_dir.clone(),
+        None => prepare_tensorflow_source(),
+    };
+
+    // Artifacts built with a different key are stale, and are rebuilt.
+    let cache_key = cache_key(&tensorflow_location);
 
     // Tell cargo to link `libm` as a dependency for tflite-micro.
     link_libm();
 
     // Build the bindings.
-    bindgen_tflite_types(&tensorflow_location);
+    bindgen_tflite_types(&tensorflow_location, &cache_key);
 
     // Build inline C++.
     build_inline_cpp(&tensorflow_location);
 
-    // Build the tensorflow library.
-    build_tflm(&tensorflow_location);
+    // Build the tensorflow library, or link the prebuilt one.
+    match &prebuilt {
+        Some(prebuilt) => link_prebuilt_tflm(prebuilt, &cache_key),
+        None => build_tflm(&tensorflow_location, &cache_key),
+    }
 }
@@ -0,0 +1,134 @@
+use crate::utils::*;
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// A TFLM library that was built outside of this crate.
+pub struct PrebuiltTflm {
+    /// The root of the TFLM headers, laid out as in a generated TFLM tree.
+    pub include_dir: PathBuf,
+    /// The directory containing `libtflm.a`, or `None` if pkg-config has
+    /// already told cargo how to link it.
+    lib_dir: Option<PathBuf>,
+}
+
+/// Find a prebuilt TFLM library, either from the `TFLM_LIB_DIR` and
+/// `TFLM_INCLUDE_DIR` environment variables, or else from the `tflm`
+/// pkg-config package if `TFLM_USE_PKG_CONFIG` is set.
+///
+/// Panics if a prebuilt library is asked for, but can't be found or was
+/// compiled with different defines.
+pub fn find_prebuilt_tflm() -> Option<PrebuiltTflm> {
+    println!("cargo:rerun-if-env-changed=TFLM_LIB_DIR");
+    println!("cargo:rerun-if-env-changed=TFLM_INCLUDE_DIR");
+    println!("cargo:rerun-if-env-changed=TFLM_LIB_DEFINES");
+    println!("cargo:rerun-if-env-changed=TFLM_USE_PKG_CONFIG");
+
+    if let Some(lib_dir) = env::var_os("TFLM_LIB_DIR").map(PathBuf::from) {
+        let include_dir = env::var_os("TFLM_INCLUDE_DIR")
+            .map(PathBuf::from)
+            .expect("TFLM_INCLUDE_DIR must be set along with TFLM_LIB_DIR");
+
+        if !lib_dir.join("libtflm.a").exists() {
+            panic!("TFLM_LIB_DIR {} has no libtflm.a", lib_dir.display());
+        }
+
+        // A static library doesn't record the defines it was compiled with,
+        // so they have to be given.
+        match env::var("TFLM_LIB_DEFINES") {
+            Ok(defines) => check_defines(defines.split([',', ' ']).filter(|d| !d.is_empty())),
+            Err(_) => println!(
+                "cargo:warning=TFLM_LIB_DEFINES is not set, so the defines of the prebuilt TFLM can't be checked. It must be compiled with {:?}",
+                abi_defines()
+            ),
+        }
+
+        return Some(PrebuiltTflm {
+            include_dir,
+            lib_dir: Some(lib_dir),
+        });
+    }
+
+    if env::var_os("TFLM_USE_PKG_CONFIG").is_some() {
+        let library = pkg_config::Config::new()
+            .statik(true)
+            .probe("tflm")
+            .unwrap_or_else(|e| panic!("Could not find TFLM with pkg-config: {}", e));
+
+        check_defines(library.defines.keys().map(String::as_str));
+
+        let include_dir = library
+            .include_paths
+            .iter()
+            .find(|p| p.join("tensorflow/lite/micro/micro_interpreter.h").exists())
+            .cloned()
+            .expect("The tflm pkg-config package has no include path containing TFLM's headers");
+
+        return Some(PrebuiltTflm {
+            include_dir,
+            lib_dir: None,
+        });
+    }
+
+    None
+}
+
+/// Check that the defines a prebuilt library was compiled with match those
+/// this crate compiles its bindings with. Only defines that change the
+/// layout of TFLM's types are compared.
+fn check_defines<'a>(lib_defines: impl Iterator<Item = &'a str>) {
+    let lib_defines: Vec<&str> = lib_defines
+        // `NAME=value` compares by name
+        .map(|d| d.split('=').next().unwrap_or(d))
+        .filter(|d| KNOWN_ABI_DEFINES.contains(d))
+        .collect();
+    let expected = abi_defines();
+
+    let missing: Vec<_> = expected
+        .iter()
+        .filter(|d| !lib_defines.contains(*d))
+        .collect();
+    let unexpected: Vec<_> = lib_defines
+        .iter()
+        .filter(|d| !expected.contains(*d))
+        .collect();
+
+    if !missing.is_empty() || !unexpected.is_empty() {
+        panic!(
+            "Prebuilt TFLM defines don't match this build: missing {:?}, unexpected {:?}",
+            missing, unexpected
+        );
+    }
+}
+
+/// Link a prebuilt TFLM library, instead of compiling it.
+pub fn link_prebuilt_tflm(prebuilt: &PrebuiltTflm, cache_key: &str) {
+    if let Some(lib_dir) = &prebuilt.lib_dir {
+        println!("Linking prebuilt TFLM from {:?}", lib_dir);
+        println!("cargo:rustc-link-search=native={}", lib_dir.display());
+        println!("cargo:rustc-link-lib=static=tflm");
+        println!(
+            "cargo:rerun-if-changed={}",
+            lib_dir.join("libtflm.a").display()
+        );
+    }
+
+    // The frontend always comes from source, as its allocations are
+    // redirected to src/frontend/memory.rs.
+    if cfg!(feature = "frontend") {
+        build_prebuilt_frontend(&prebuilt.include_dir, cache_key);
+    }
+}
+
+fn build_prebuilt_frontend(include_dir: &Path, cache_key: &str) {
+    if !include_dir
+        .join("tensorflow/lite/experimental/microfrontend/lib")
+        .is_dir()
+    {
+        panic!(
+            "The frontend feature needs the microfrontend and kissfft sources in {}",
+            include_dir.display()
+        );
+    }
+
+    crate::compile::build_frontend(include_dir, cache_key);
+}
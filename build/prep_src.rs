@@ -1,5 +1,6 @@
+use super::cache::*;
 use super::utils::*;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 /// Move the tensorflow source to the build directory so that we can build it
@@ -8,16 +9,33 @@ pub fn prepare_tensorflow_source() -> PathBuf {
     println!("Preparing TFLM source...");
 
     let start: Instant = Instant::now();
-    let tflm_out_dir = OUT_DIR.join("tflite-micro");
+
+    // A pre-generated tree is used as is, without git, make or python.
+    if let Some(tree) = pre_generated_tree() {
+        println!("Using pre-generated TFLM tree {:?}", tree);
+        check_pre_generated_tree(&tree);
+        return tree;
+    }
+
+    // Trees with optimized kernels are generated separately, as they
+    // contain different sources.
+    let tflm_out_dir = match optimized_kernel_dir() {
+        Some(dir) => OUT_DIR.join(format!("tflite-micro-{}", dir)),
+        None => OUT_DIR.join("tflite-micro"),
+    };
 
     // Begin the build process: checkout the TFLM library git repo if it's not
     // already there and update it if it is.
     checkout_tflm_repo();
 
-    // If the folder is already there *AND* we're building it, delete it.
+    // If the folder is already there *AND* we're building it, or it was
+    // generated from another revision, delete it.
     // Note that below, we only check !tflm_out_dir.exists() because if we're
     // building it from scratch, we delete the folder anyways.
-    if tflm_out_dir.exists() && cfg!(feature = "build") {
+    let revision = source_revision().unwrap_or_else(|| "unknown".to_string());
+    let stamp = tflm_out_dir.join(REVISION_STAMP);
+    let stale = std::fs::read_to_string(&stamp).map_or(true, |r| r != revision);
+    if tflm_out_dir.exists() && (stale || cfg!(feature = "build")) {
         println!("Deleting old TFLM source...");
         fs_extra::dir::remove(tflm_out_dir.as_path()).expect("Unable to delete old TFLM source");
     }
@@ -47,14 +65,21 @@ pub fn prepare_tensorflow_source() -> PathBuf {
     // generate the source files -- this script integrates TFLM into a project (apparently).
     // If we're not building the library, we don't need to do this.
     if !tflm_out_dir.exists() {
-        run_command_or_fail(
-            &TENSORFLOW_LOCATION,
-            "python3",
-            &[
-                "tensorflow/lite/micro/tools/project_generation/create_tflm_tree.py",
-                &tflm_out_dir.to_string_lossy(),
-            ],
-        );
+        let mut args =
+            vec!["tensorflow/lite/micro/tools/project_generation/create_tflm_tree.py".to_string()];
+        if let Some(dir) = optimized_kernel_dir() {
+            args.push(format!("--makefile_options=OPTIMIZED_KERNEL_DIR={}", dir));
+        }
+        args.push(tflm_out_dir.to_string_lossy().to_string());
+
+        run_command_or_fail(&TENSORFLOW_LOCATION, "python3", &args);
+        std::fs::write(&stamp, &revision).expect("Unable to write TFLM revision");
+    }
+
+    // The generated tree only contains the core library, so copy in the
+    // audio frontend and its FFT library.
+    if cfg!(feature = "frontend") {
+        copy_frontend_source(&tflm_out_dir);
     }
 
     println!("Preparing source took {:?}", start.elapsed());
@@ -62,6 +87,82 @@ pub fn prepare_tensorflow_source() -> PathBuf {
     tflm_out_dir
 }
 
+/// Returns the pre-generated TFLM tree to build, if any. `TFLM_SOURCE_DIR`
+/// takes precedence over a tree vendored in the package.
+fn pre_generated_tree() -> Option<PathBuf> {
+    if let Some(dir) = TFLM_SOURCE_DIR.as_ref() {
+        return Some(dir.clone());
+    }
+
+    let vendored = VENDORED_TFLM_LOCATION.canonicalize().ok()?;
+    vendored.is_dir().then_some(vendored)
+}
+
+/// Check that a pre-generated tree contains everything the build needs, so
+/// that a stale or incomplete tree fails early with a clear message, rather
+/// than with a compiler error.
+fn check_pre_generated_tree(tree: &Path) {
+    let mut required = vec![
+        "tensorflow/lite/micro/micro_interpreter.h",
+        "third_party/flatbuffers/include",
+        "third_party/gemmlowp",
+        "third_party/ruy",
+    ];
+    if cfg!(feature = "frontend") {
+        required.push("tensorflow/lite/experimental/microfrontend/lib");
+        required.push("third_party/kissfft");
+    }
+
+    let optimized_kernels =
+        optimized_kernel_dir().map(|dir| format!("tensorflow/lite/micro/kernels/{}", dir));
+    required.extend(optimized_kernels.as_deref());
+
+    let missing: Vec<&str> = required
+        .into_iter()
+        .filter(|p| !tree.join(p).exists())
+        .collect();
+
+    if !missing.is_empty() {
+        panic!(
+            "Pre-generated TFLM tree {} is missing {:?}. See \"Offline builds\" in the README.",
+            tree.display(),
+            missing
+        );
+    }
+}
+
+/// Copy the audio frontend sources, and kissfft which it depends on, from
+/// the TFLM repo into the generated tree.
+fn copy_frontend_source(tflm_out_dir: &Path) {
+    let copy_options = fs_extra::dir::CopyOptions {
+        overwrite: true,
+        copy_inside: true,
+        ..Default::default()
+    };
+
+    let frontend_dest = tflm_out_dir.join("tensorflow/lite/experimental/microfrontend/lib");
+    if !frontend_dest.exists() {
+        std::fs::create_dir_all(frontend_dest.parent().unwrap())
+            .expect("Unable to create frontend directory");
+        fs_extra::dir::copy(
+            TENSORFLOW_LOCATION.join("tensorflow/lite/experimental/microfrontend/lib"),
+            &frontend_dest,
+            &copy_options,
+        )
+        .expect("Unable to copy frontend source");
+    }
+
+    let kissfft_dest = tflm_out_dir.join("third_party/kissfft");
+    if !kissfft_dest.exists() {
+        fs_extra::dir::copy(
+            TENSORFLOW_LOCATION.join("tensorflow/lite/micro/tools/make/downloads/kissfft"),
+            &kissfft_dest,
+            &copy_options,
+        )
+        .expect("Unable to copy kissfft source");
+    }
+}
+
 /// `git checkout` the TFLM library if it's not already there. Also download thrid-party tools
 /// and/or install pip packages.
 fn checkout_tflm_repo() {
@@ -88,13 +189,15 @@ fn checkout_tflm_repo() {
     // }
 
     // See #199: https://github.com/tensorflow/tflite-micro/issues/199
-    run_command_or_fail(
-        &TENSORFLOW_LOCATION,
-        "make",
-        &[
-            "-f",
-            "tensorflow/lite/micro/tools/make/Makefile",
-            "third_party_downloads",
-        ],
-    );
+    let mut args = vec![
+        "-f".to_string(),
+        "tensorflow/lite/micro/tools/make/Makefile".to_string(),
+    ];
+    // Optimized kernels may need their own third party libraries.
+    if let Some(dir) = optimized_kernel_dir() {
+        args.push(format!("OPTIMIZED_KERNEL_DIR={}", dir));
+    }
+    args.push("third_party_downloads".to_string());
+
+    run_command_or_fail(&TENSORFLOW_LOCATION, "make", &args);
 }
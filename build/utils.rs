@@ -11,6 +11,19 @@ lazy_static! {
     /// The location of the TFLM source directory.
     pub static ref TENSORFLOW_LOCATION: PathBuf = PathBuf::from("c/tflite-micro");
 
+    /// The location of a pre-generated TFLM tree, shipped in the package so
+    /// that it builds without git, network access or python.
+    pub static ref VENDORED_TFLM_LOCATION: PathBuf = PathBuf::from("c/tflm-tree");
+
+    /// A pre-generated TFLM tree given by the `TFLM_SOURCE_DIR` environment
+    /// variable, if any.
+    pub static ref TFLM_SOURCE_DIR: Option<PathBuf> = {
+        println!("cargo:rerun-if-env-changed=TFLM_SOURCE_DIR");
+        env::var_os("TFLM_SOURCE_DIR")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+    };
+
     /// The target triple of the current build.
     pub static ref TARGET: String = env::var("TARGET").expect("Could not get target triple!");
 
@@ -19,8 +32,38 @@ lazy_static! {
 
     /// The output directory of the current build.
     pub static ref OUT_DIR: PathBuf = PathBuf::from(env::var("OUT_DIR").expect("Could not get output directory!"));
+
+    /// The optimized kernel directory selected by features or by the
+    /// `TFLM_OPTIMIZED_KERNEL_DIR` environment variable, if any.
+    pub static ref OPTIMIZED_KERNEL_DIR: Option<String> = select_optimized_kernel_dir();
+
+    /// Extra compiler flags from the `TFLM_CFLAGS` environment variable,
+    /// separated by whitespace.
+    pub static ref TFLM_CFLAGS: Vec<String> = env_list("TFLM_CFLAGS", char::is_whitespace);
+
+    /// Extra defines from the `TFLM_DEFINES` environment variable, separated
+    /// by whitespace or commas, each either `NAME` or `NAME=value`.
+    pub static ref TFLM_DEFINES: Vec<(String, Option<String>)> =
+        env_list("TFLM_DEFINES", |c| c.is_whitespace() || c == ',')
+            .into_iter()
+            .map(|define| match define.split_once('=') {
+                Some((name, value)) => (name.to_string(), Some(value.to_string())),
+                None => (define, None),
+            })
+            .collect();
 }
 
+/// Optimized kernel directories shipped by TFLM, and the features that
+/// select them.
+const OPTIMIZED_KERNEL_FEATURES: &[(&str, bool)] = &[
+    ("cmsis_nn", cfg!(feature = "cmsis-nn")),
+    ("xtensa", cfg!(feature = "xtensa")),
+    ("ethos_u", cfg!(feature = "ethos-u")),
+    ("ceva", cfg!(feature = "ceva")),
+    ("arc_mli", cfg!(feature = "arc-mli")),
+    ("portable_optimized", cfg!(feature = "portable-optimized")),
+];
+
 impl fmt::Display for TENSORFLOW_LOCATION {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", TENSORFLOW_LOCATION.display())
@@ -29,7 +72,7 @@ impl fmt::Display for TENSORFLOW_LOCATION {
 
 impl fmt::Display for TARGET {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", TARGET)
+        write!(f, "{}", self.as_str())
     }
 }
 
@@ -65,6 +108,13 @@ pub trait CompilationBuilder {
             .define("TF_LITE_MCU_DEBUG_LOG", None)
             .define("GEMMLOWP_ALLOW_SLOW_SCALAR_FALLBACK", None);
 
+        // Strip error strings to save flash.
+        let build = if cfg!(feature = "minimal") {
+            build.define("TF_LITE_STRIP_ERROR_STRINGS", None)
+        } else {
+            build
+        };
+
         // warnings on by default
         let build = if cfg!(feature = "no-c-warnings") {
             build.flag("-w")
@@ -80,15 +130,163 @@ pub trait CompilationBuilder {
                 .flag("-Wunused-function")
         };
 
-        if TARGET.starts_with("thumb") {
+        // Optimized kernels. The defines must match between the kernels
+        // and the inline C++, so they're set up here.
+        let build = match optimized_kernel_dir() {
+            Some("cmsis_nn") => {
+                let (flags, defines) = cmsis_nn_arch_flags();
+
+                let build = build.define("CMSIS_NN", None);
+                let build = flags.iter().fold(build, |build, flag| build.flag(flag));
+                defines
+                    .iter()
+                    .fold(build, |build, define| build.define(define, None))
+            }
+            // CEVA's defines depend on the core, eg. `CEVA_BX1`, so must be
+            // provided by the user.
+            dir => match optimized_kernel_define(dir) {
+                Some(define) => build.define(define, None),
+                None => build,
+            },
+        };
+
+        let build = if TARGET.starts_with("thumb") {
             // unaligned accesses are usually a poor idea on ARM cortex-m
             build.flag("-mno-unaligned-access")
         } else {
             build
-        }
+        };
+
+        // The user's flags and defines come last, so that they override
+        // those above, eg. `-O3` over the optimization level from cargo.
+        let build = TFLM_CFLAGS
+            .iter()
+            .fold(build, |build, flag| build.flag(flag));
+        TFLM_DEFINES.iter().fold(build, |build, (name, value)| {
+            build.define(name, value.as_deref())
+        })
+    }
+}
+
+/// Read a list from the environment variable `var`, separated by the
+/// characters matching `separator`. Compiler flags may contain commas, eg.
+/// `-Wl,--gc-sections`, so are only separated by whitespace.
+fn env_list(var: &str, separator: fn(char) -> bool) -> Vec<String> {
+    println!("cargo:rerun-if-env-changed={}", var);
+
+    env::var(var)
+        .unwrap_or_default()
+        .split(separator)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// The define that selects the optimized kernels in `dir` in TFLM's
+/// headers, if any.
+pub fn optimized_kernel_define(dir: Option<&str>) -> Option<&'static str> {
+    match dir? {
+        "cmsis_nn" => Some("CMSIS_NN"),
+        "xtensa" => Some("XTENSA"),
+        "ethos_u" => Some("ETHOS_U"),
+        "arc_mli" => Some("ARC_MLI"),
+        _ => None,
+    }
+}
+
+/// Defines that change the layout of TFLM's types, and so must match
+/// between the library, the bindings and the inline C++.
+pub fn abi_defines() -> Vec<&'static str> {
+    let mut defines = vec!["TF_LITE_STATIC_MEMORY"];
+    defines.extend(optimized_kernel_define(optimized_kernel_dir()));
+    defines
+}
+
+/// Every define that [`abi_defines`] may return, whichever features are
+/// enabled.
+pub const KNOWN_ABI_DEFINES: &[&str] = &[
+    "TF_LITE_STATIC_MEMORY",
+    "CMSIS_NN",
+    "XTENSA",
+    "ETHOS_U",
+    "ARC_MLI",
+];
+
+/// The optimized kernel directory to compile in place of the reference
+/// kernels, as with the `OPTIMIZED_KERNEL_DIR` option of TFLM's Makefile.
+pub fn optimized_kernel_dir() -> Option<&'static str> {
+    OPTIMIZED_KERNEL_DIR.as_deref()
+}
+
+/// Select the optimized kernel directory from the `TFLM_OPTIMIZED_KERNEL_DIR`
+/// environment variable, or else from the enabled features. The variable
+/// takes precedence over features, and setting it to `reference` selects
+/// the reference kernels.
+///
+/// Panics if more than one directory is selected.
+fn select_optimized_kernel_dir() -> Option<String> {
+    println!("cargo:rerun-if-env-changed=TFLM_OPTIMIZED_KERNEL_DIR");
+
+    let from_features: Vec<&str> = OPTIMIZED_KERNEL_FEATURES
+        .iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(dir, _)| *dir)
+        .collect();
+
+    if from_features.len() > 1 {
+        panic!(
+            "Only one optimized kernel feature may be enabled, found {:?}",
+            from_features
+        );
+    }
+
+    match env::var("TFLM_OPTIMIZED_KERNEL_DIR") {
+        Ok(dir) if dir.is_empty() || dir == "reference" => None,
+        Ok(dir) => Some(dir),
+        Err(_) => from_features.first().map(|dir| dir.to_string()),
     }
 }
 
+/// Returns the `-march` flags and defines that enable the DSP and MVE
+/// extensions used by CMSIS-NN, depending on the target, with the FPU of
+/// hard-float targets. Targets that aren't Cortex-M get neither, and use
+/// CMSIS-NN's portable C code.
+pub fn cmsis_nn_arch_flags() -> (Vec<String>, Vec<&'static str>) {
+    let hard_float = TARGET.ends_with("eabihf");
+
+    // Longer prefixes are checked first
+    let (march, defines): (String, &[&'static str]) = if TARGET.starts_with("thumbv8.1m.main") {
+        let mve = if hard_float { "+mve.fp" } else { "+mve" };
+        (
+            format!("armv8.1-m.main+dsp{}", mve),
+            &["ARM_MATH_DSP", "ARM_MATH_MVEI"],
+        )
+    } else if TARGET.starts_with("thumbv8m.main") {
+        let fp = if hard_float { "+fp" } else { "" };
+        (format!("armv8-m.main+dsp{}", fp), &["ARM_MATH_DSP"])
+    } else if TARGET.starts_with("thumbv8m.base") {
+        ("armv8-m.base".to_string(), &[])
+    } else if TARGET.starts_with("thumbv7em") {
+        let fp = if hard_float { "+fp" } else { "" };
+        (format!("armv7e-m{}", fp), &["ARM_MATH_DSP"])
+    } else if TARGET.starts_with("thumbv7m") {
+        ("armv7-m".to_string(), &[])
+    } else if TARGET.starts_with("thumbv6m") {
+        ("armv6-m".to_string(), &[])
+    } else {
+        return (vec![], vec![]);
+    };
+
+    // Rust's hard-float targets pass floats in FP registers, so the C code
+    // must use the same ABI
+    let mut flags = vec![format!("-march={}", march)];
+    if hard_float {
+        flags.push("-mfloat-abi=hard".to_string());
+    }
+
+    (flags, defines.to_vec())
+}
+
 impl CompilationBuilder for cpp_build::Config {
     fn flag(&mut self, s: &str) -> &mut Self {
         self.flag(s)
@@ -0,0 +1,137 @@
+# Writes signatures.tflite, a model with a `serving_default` SignatureDef
+# for testing signature lookups. It has no operators, so it can't be run.
+#
+# The main subgraph has inputs x, y and outputs a, b. The signature lists
+# them in the opposite order, under different names:
+#
+#   inputs:  y_in -> y (input 1), x_in -> x (input 0)
+#   outputs: b_out -> b (output 1), a_out -> a (output 0)
+#
+# The flatbuffer is written by hand, following tensorflow/lite/schema/schema.fbs,
+# as every object is placed after the object that refers to it.
+
+import struct
+
+buf = bytearray()
+
+
+def align(n):
+    while len(buf) % n:
+        buf.append(0)
+
+
+def patch(at, target):
+    struct.pack_into("<I", buf, at, target - at)
+
+
+def string(s):
+    align(4)
+    pos = len(buf)
+    data = s.encode()
+    buf.extend(struct.pack("<I", len(data)) + data + b"\0")
+    return pos
+
+
+def int_vector(values):
+    align(4)
+    pos = len(buf)
+    buf.extend(struct.pack("<I", len(values)))
+    for v in values:
+        buf.extend(struct.pack("<i", v))
+    return pos
+
+
+def vector(writers):
+    align(4)
+    pos = len(buf)
+    buf.extend(struct.pack("<I", len(writers)))
+    slots = []
+    for _ in writers:
+        slots.append(len(buf))
+        buf.extend(b"\0\0\0\0")
+    for slot, write in zip(slots, writers):
+        patch(slot, write())
+    return pos
+
+
+def table(fields):
+    """`fields` maps field ids to ("u32", value), ("u8", value) or
+    ("offset", writer). Every field takes 4 bytes inline."""
+    count = max(fields) + 1 if fields else 0
+    order = sorted(fields)
+
+    # vtable: its size, the table's size, then each field's offset
+    align(2)
+    vtable = len(buf)
+    offsets = [0] * count
+    for i, id in enumerate(order):
+        offsets[id] = 4 + 4 * i
+    buf.extend(struct.pack("<HH", 4 + 2 * count, 4 + 4 * len(order)))
+    for offset in offsets:
+        buf.extend(struct.pack("<H", offset))
+
+    align(4)
+    pos = len(buf)
+    buf.extend(struct.pack("<i", pos - vtable))
+
+    children = []
+    for id in order:
+        kind, value = fields[id]
+        if kind == "u32":
+            buf.extend(struct.pack("<I", value))
+        elif kind == "u8":
+            buf.extend(struct.pack("<B", value) + b"\0\0\0")
+        else:
+            children.append((len(buf), value))
+            buf.extend(b"\0\0\0\0")
+
+    for slot, write in children:
+        patch(slot, write())
+    return pos
+
+
+def tensor(name):
+    return lambda: table({
+        0: ("offset", lambda: int_vector([1])),  # shape
+        1: ("u8", 0),  # type: FLOAT32
+        2: ("u32", 0),  # buffer
+        3: ("offset", lambda: string(name)),  # name
+    })
+
+
+def tensor_map(name, tensor_index):
+    return lambda: table({
+        0: ("offset", lambda: string(name)),
+        1: ("u32", tensor_index),
+    })
+
+
+subgraph = lambda: table({
+    0: ("offset", lambda: vector([tensor(n) for n in ["x", "y", "a", "b"]])),
+    1: ("offset", lambda: int_vector([0, 1])),  # inputs
+    2: ("offset", lambda: int_vector([2, 3])),  # outputs
+    3: ("offset", lambda: vector([])),  # operators
+    4: ("offset", lambda: string("main")),
+})
+
+signature = lambda: table({
+    0: ("offset", lambda: vector([tensor_map("y_in", 1), tensor_map("x_in", 0)])),
+    1: ("offset", lambda: vector([tensor_map("b_out", 3), tensor_map("a_out", 2)])),
+    2: ("offset", lambda: string("serving_default")),
+    4: ("u32", 0),  # subgraph_index
+})
+
+model = lambda: table({
+    0: ("u32", 3),  # version
+    1: ("offset", lambda: vector([])),  # operator_codes
+    2: ("offset", lambda: vector([subgraph])),
+    4: ("offset", lambda: vector([lambda: table({})])),  # buffers
+    7: ("offset", lambda: vector([signature])),
+})
+
+# Root offset, then the file identifier
+buf.extend(b"\0\0\0\0TFL3")
+patch(0, model())
+
+with open("examples/models/signatures.tflite", "wb") as output_file:
+    output_file.write(buf)
@@ -0,0 +1,13 @@
+MEMORY
+{
+  /* NOTE K = KiBi = 1024 bytes */
+  /* Large enough for any build to link, the size report compares against
+     the real flash size */
+  FLASH : ORIGIN = 0x08000000, LENGTH = 1024K
+  RAM : ORIGIN = 0x20000000, LENGTH = 16K
+}
+
+/* This is where the call stack will be allocated. */
+/* The stack is of the full descending type. */
+/* NOTE Do NOT modify `_stack_start` unless you know what you are doing */
+_stack_start = ORIGIN(RAM) + LENGTH(RAM);
@@ -0,0 +1,38 @@
+//! The hello_world model on a Cortex-M0, for measuring the size of the
+//! crate. See tooling/size.sh
+
+#![no_main]
+#![no_std]
+
+use panic_halt as _;
+
+use cortex_m_rt::entry;
+use tflite_micro::{MicroInterpreter, Model};
+
+#[entry]
+fn main() -> ! {
+    let model = include_bytes!("../../models/hello_world.tflite");
+    let model = Model::from_buffer(&model[..]).unwrap();
+
+    const TENSOR_ARENA_SIZE: usize = 4 * 1024;
+    let mut tensor_arena: [u8; TENSOR_ARENA_SIZE] = [0; TENSOR_ARENA_SIZE];
+
+    // With `all-ops`, every operator is linked, as an application using
+    // `AllOpResolver` would
+    #[cfg(feature = "all-ops")]
+    let micro_op_resolver = tflite_micro::AllOpResolver::new();
+    #[cfg(not(feature = "all-ops"))]
+    let micro_op_resolver = tflite_micro::MutableOpResolver::empty().fully_connected();
+
+    let mut interpreter =
+        MicroInterpreter::new(&model, &micro_op_resolver, &mut tensor_arena[..]).unwrap();
+
+    let mut x = 0.0f32;
+    loop {
+        interpreter.input(0, &[x]).unwrap();
+        interpreter.invoke().unwrap();
+
+        let y: f32 = interpreter.output(0).as_data()[0];
+        x = y + 0.1;
+    }
+}
@@ -0,0 +1,15 @@
+#!/bin/awk -f
+#
+# Print the flash usage of each section, and in total against a 64 KiB part,
+# as the cells of a markdown table row
+
+BEGIN { SIZE = 64 * 1024 }
+{
+  if ($1 == ".text" || $1 == ".rodata" || $1 == ".data") {
+    SECTION[$1] = $2
+    FLASH += $2
+  }
+}
+END {
+  printf "%d | %d | %d | %d | %.1f%%\n", SECTION[".text"], SECTION[".rodata"], SECTION[".data"], FLASH, 100 * FLASH / SIZE
+}
@@ -0,0 +1,29 @@
+#!/bin/bash
+#
+# Compare the size of the hello_world model on thumbv6m with every operator,
+# and with the `minimal` feature and only FULLY_CONNECTED. Prints a markdown
+# table, as in README.md
+
+DIR="$( cd "$( dirname "${BASH_SOURCE[0]}" )" >/dev/null 2>&1 && pwd )"
+cd $DIR/..
+
+set -e
+
+BINARY=target/thumbv6m-none-eabi/release/tflite-micro-size-report
+
+cargo build --release --no-default-features --features all-ops
+FULL=$(arm-none-eabi-size -Ad $BINARY | awk -f $DIR/size.awk -)
+
+cargo build --release --no-default-features --features minimal
+MINIMAL=$(arm-none-eabi-size -Ad $BINARY | awk -f $DIR/size.awk -)
+
+echo "| Variant | .text | .rodata | .data | Flash | Of 64 KiB |"
+echo "| ------- | ----: | ------: | ----: | ----: | --------: |"
+echo "| \`all-ops\` | $FULL |"
+echo "| \`minimal\` + \`op-fully-connected\` | $MINIMAL |"
+
+# Flash is the fourth cell of each row
+FULL_FLASH=$(echo "$FULL" | awk -F' \\| ' '{ print $4 }')
+MINIMAL_FLASH=$(echo "$MINIMAL" | awk -F' \\| ' '{ print $4 }')
+echo
+echo "Saved: $((FULL_FLASH - MINIMAL_FLASH)) bytes"
@@ -1,5 +1,146 @@
-#![allow(dead_code, clippy::all)]
+#![allow(
+    dead_code,
+    non_camel_case_types,
+    non_snake_case,
+    non_upper_case_globals,
+    improper_ctypes,
+    clippy::all
+)]
 
 pub use self::root::*;
 
 include!(concat!(env!("OUT_DIR"), "/tflite_types.rs"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ffi::CStr;
+    use core::mem::size_of;
+    use core::ptr;
+
+    cpp! {{
+        #include <array>
+        #include "tensorflow/lite/c/common.h"
+        #include "tensorflow/lite/c/builtin_op_data.h"
+    }}
+
+    #[test]
+    fn c_api_type_sizes() {
+        // The C++ compiler lays these out itself, so must agree with bindgen
+        let sizes = unsafe {
+            cpp!([] -> [usize; 6] as "std::array<size_t, 6>" {
+                return {
+                    sizeof(TfLiteTensor),
+                    sizeof(TfLiteEvalTensor),
+                    sizeof(TfLiteAffineQuantization),
+                    sizeof(TfLiteNode),
+                    sizeof(TfLiteConvParams),
+                    sizeof(TfLiteFullyConnectedParams),
+                };
+            })
+        };
+
+        assert_eq!(
+            sizes,
+            [
+                size_of::<TfLiteTensor>(),
+                size_of::<TfLiteEvalTensor>(),
+                size_of::<TfLiteAffineQuantization>(),
+                size_of::<TfLiteNode>(),
+                size_of::<TfLiteConvParams>(),
+                size_of::<TfLiteFullyConnectedParams>(),
+            ]
+        );
+    }
+
+    #[test]
+    fn builtin_options_from_rust() {
+        let params = TfLiteConvParams {
+            padding: TfLitePadding::kTfLitePaddingValid,
+            stride_width: 2,
+            stride_height: 3,
+            dilation_width_factor: 4,
+            dilation_height_factor: 5,
+            activation: TfLiteFusedActivation::kTfLiteActRelu6,
+            ..Default::default()
+        };
+        let params_ref = &params;
+
+        let fields = unsafe {
+            cpp!([params_ref as "const TfLiteConvParams*"] -> [i32; 6] as "std::array<int32_t, 6>" {
+                return {
+                    static_cast<int32_t>(params_ref->padding),
+                    static_cast<int32_t>(params_ref->stride_width),
+                    static_cast<int32_t>(params_ref->stride_height),
+                    static_cast<int32_t>(params_ref->dilation_width_factor),
+                    static_cast<int32_t>(params_ref->dilation_height_factor),
+                    static_cast<int32_t>(params_ref->activation),
+                };
+            })
+        };
+
+        assert_eq!(
+            fields,
+            [
+                TfLitePadding::kTfLitePaddingValid as i32,
+                2,
+                3,
+                4,
+                5,
+                TfLiteFusedActivation::kTfLiteActRelu6 as i32
+            ]
+        );
+    }
+
+    #[test]
+    fn builtin_options_from_cpp() {
+        let params = unsafe {
+            cpp!([] -> TfLiteFullyConnectedParams as "TfLiteFullyConnectedParams" {
+                TfLiteFullyConnectedParams params = {};
+                params.activation = kTfLiteActRelu;
+                params.weights_format = kTfLiteFullyConnectedWeightsFormatShuffled4x16Int8;
+                params.keep_num_dims = true;
+                params.asymmetric_quantize_inputs = false;
+                return params;
+            })
+        };
+
+        assert_eq!(params.activation, TfLiteFusedActivation::kTfLiteActRelu);
+        assert_eq!(
+            params.weights_format,
+            TfLiteFullyConnectedWeightsFormat::kTfLiteFullyConnectedWeightsFormatShuffled4x16Int8
+        );
+        assert!(params.keep_num_dims);
+        assert!(!params.asymmetric_quantize_inputs);
+    }
+
+    #[test]
+    fn int_array() {
+        let size = unsafe { TfLiteIntArrayGetSizeInBytes(3) } as usize;
+        assert_eq!(size, size_of::<TfLiteIntArray>() + 3 * size_of::<i32>());
+
+        // An array of 3, laid out as TensorFlow would in the arena
+        let mut storage = [0i32; 8];
+        assert!(size <= size_of::<[i32; 8]>());
+        let array = storage.as_mut_ptr() as *mut TfLiteIntArray;
+        unsafe {
+            (*array).size = 3;
+            let data = ptr::addr_of_mut!((*array).data) as *mut i32;
+            data.copy_from_nonoverlapping([1, 2, 3].as_ptr(), 3);
+        }
+
+        unsafe {
+            assert_eq!(TfLiteIntArrayEqualsArray(array, 3, [1, 2, 3].as_ptr()), 1);
+            assert_eq!(TfLiteIntArrayEqualsArray(array, 3, [1, 2, 4].as_ptr()), 0);
+            assert_eq!(TfLiteIntArrayEqualsArray(array, 2, [1, 2].as_ptr()), 0);
+            assert_eq!(TfLiteIntArrayEqual(array, array), 1);
+        }
+    }
+
+    #[test]
+    fn type_name() {
+        let name = unsafe { CStr::from_ptr(TfLiteTypeGetName(TfLiteType::kTfLiteFloat32)) };
+
+        assert_eq!(name.to_str(), Ok("FLOAT32"));
+    }
+}
@@ -0,0 +1,348 @@
+//! Configuration for the audio frontend
+//!
+//! [`FrontendConfig`](FrontendConfig) mirrors every field of the C
+//! `FrontendConfig` struct, plus the sample rate that is passed to
+//! `FrontendPopulateState`. The defaults are those used by the
+//! micro_speech example.
+
+use crate::bindings;
+
+/// Errors that can occour when configuring, creating or using a
+/// [`Frontend`](super::Frontend)
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FrontendError {
+    /// The sample rate was zero
+    InvalidSampleRate,
+    /// The window size or step was zero, the step was larger than the
+    /// window, or the window contains no samples at this sample rate
+    InvalidWindow,
+    /// The number of filterbank channels was zero
+    InvalidChannelCount,
+    /// The band limits were not `0 <= lower < upper <= sample_rate / 2`
+    InvalidBandLimits,
+    /// A noise reduction parameter was out of range
+    InvalidNoiseReduction,
+    /// A PCAN gain control parameter was out of range
+    InvalidPcanGainControl,
+    /// A log scale parameter was out of range
+    InvalidLogScale,
+    /// TensorFlow failed to populate the frontend state
+    PopulateStateError,
+    /// The output slice was not the configured number of channels long
+    OutputLenMismatch,
+}
+
+/// Configuration for a [`Frontend`](super::Frontend), built in a builder
+/// pattern.
+///
+/// # Usage
+///
+/// ```rust
+/// # use tflite_micro::FrontendConfig;
+/// let config = FrontendConfig::new()
+///     .sample_rate(8_000)
+///     .window(25, 10)
+///     .num_channels(32)
+///     .band_limits(125.0, 3800.0);
+///
+/// assert!(config.validate().is_ok());
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct FrontendConfig {
+    sample_rate: u32,
+
+    // Window
+    window_size_ms: usize,
+    window_step_ms: usize,
+
+    // Filterbank
+    num_channels: i32,
+    lower_band_limit: f32,
+    upper_band_limit: f32,
+    output_scale_shift: i32,
+
+    // Noise reduction
+    smoothing_bits: i32,
+    even_smoothing: f32,
+    odd_smoothing: f32,
+    min_signal_remaining: f32,
+
+    // PCAN gain control
+    enable_pcan: bool,
+    pcan_strength: f32,
+    pcan_offset: f32,
+    pcan_gain_bits: i32,
+
+    // Log scale
+    enable_log: bool,
+    log_scale_shift: i32,
+}
+
+impl Default for FrontendConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FrontendConfig {
+    /// Create a new configuration, populated with the values used by the
+    /// micro_speech example: 16kHz audio, a 30ms window every 20ms and 40
+    /// channels between 125Hz and 7500Hz
+    pub const fn new() -> Self {
+        Self {
+            sample_rate: 16_000,
+
+            window_size_ms: 30,
+            window_step_ms: 20,
+
+            num_channels: 40,
+            lower_band_limit: 125.0,
+            upper_band_limit: 7500.0,
+            output_scale_shift: 7,
+
+            smoothing_bits: 10,
+            even_smoothing: 0.025,
+            odd_smoothing: 0.06,
+            min_signal_remaining: 0.05,
+
+            enable_pcan: true,
+            pcan_strength: 0.95,
+            pcan_offset: 80.0,
+            pcan_gain_bits: 21,
+
+            enable_log: true,
+            log_scale_shift: 6,
+        }
+    }
+
+    /// Set the sample rate of the input audio, in Hz
+    pub const fn sample_rate(mut self, sample_rate: u32) -> Self {
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    /// Set the window size and the step between windows, in milliseconds
+    pub const fn window(mut self, size_ms: usize, step_ms: usize) -> Self {
+        self.window_size_ms = size_ms;
+        self.window_step_ms = step_ms;
+        self
+    }
+
+    /// Set the number of filterbank channels. This is the length of each
+    /// feature vector
+    pub const fn num_channels(mut self, num_channels: i32) -> Self {
+        self.num_channels = num_channels;
+        self
+    }
+
+    /// Set the lowest and highest frequencies included in the filterbank,
+    /// in Hz
+    pub const fn band_limits(mut self, lower: f32, upper: f32) -> Self {
+        self.lower_band_limit = lower;
+        self.upper_band_limit = upper;
+        self
+    }
+
+    /// Set the filterbank output scale shift. This is unused by
+    /// TensorFlow, but is kept for completeness
+    pub const fn output_scale_shift(mut self, shift: i32) -> Self {
+        self.output_scale_shift = shift;
+        self
+    }
+
+    /// Set the noise reduction parameters
+    pub const fn noise_reduction(
+        mut self,
+        smoothing_bits: i32,
+        even_smoothing: f32,
+        odd_smoothing: f32,
+        min_signal_remaining: f32,
+    ) -> Self {
+        self.smoothing_bits = smoothing_bits;
+        self.even_smoothing = even_smoothing;
+        self.odd_smoothing = odd_smoothing;
+        self.min_signal_remaining = min_signal_remaining;
+        self
+    }
+
+    /// Enable PCAN (per-channel amplitude normalization) gain control with
+    /// the given parameters
+    pub const fn pcan_gain_control(mut self, strength: f32, offset: f32, gain_bits: i32) -> Self {
+        self.enable_pcan = true;
+        self.pcan_strength = strength;
+        self.pcan_offset = offset;
+        self.pcan_gain_bits = gain_bits;
+        self
+    }
+
+    /// Disable PCAN gain control
+    pub const fn disable_pcan(mut self) -> Self {
+        self.enable_pcan = false;
+        self
+    }
+
+    /// Enable log scaling of the output, with the given scale shift
+    pub const fn log_scale(mut self, scale_shift: i32) -> Self {
+        self.enable_log = true;
+        self.log_scale_shift = scale_shift;
+        self
+    }
+
+    /// Disable log scaling of the output
+    pub const fn disable_log(mut self) -> Self {
+        self.enable_log = false;
+        self
+    }
+
+    /// Returns the configured sample rate, in Hz
+    pub fn get_sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Returns the configured number of channels
+    pub fn get_num_channels(&self) -> usize {
+        self.num_channels as usize
+    }
+
+    /// Returns the number of samples in each window
+    pub fn window_size_samples(&self) -> usize {
+        self.window_size_ms * self.sample_rate as usize / 1000
+    }
+
+    /// Returns the number of samples between the start of each window
+    pub fn window_step_samples(&self) -> usize {
+        self.window_step_ms * self.sample_rate as usize / 1000
+    }
+
+    /// Check that this configuration can be used to create a
+    /// [`Frontend`](super::Frontend)
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`FrontendError`](FrontendError) describing the first
+    /// invalid parameter found.
+    pub fn validate(&self) -> Result<(), FrontendError> {
+        if self.sample_rate == 0 {
+            return Err(FrontendError::InvalidSampleRate);
+        }
+
+        if self.window_size_ms == 0
+            || self.window_step_ms == 0
+            || self.window_step_ms > self.window_size_ms
+            || self.window_step_samples() == 0
+        {
+            return Err(FrontendError::InvalidWindow);
+        }
+
+        if self.num_channels <= 0 {
+            return Err(FrontendError::InvalidChannelCount);
+        }
+
+        let nyquist = self.sample_rate as f32 / 2.0;
+        if !(self.lower_band_limit >= 0.0
+            && self.lower_band_limit < self.upper_band_limit
+            && self.upper_band_limit <= nyquist)
+        {
+            return Err(FrontendError::InvalidBandLimits);
+        }
+
+        let unit = 0.0..=1.0;
+        if !(0..32).contains(&self.smoothing_bits)
+            || !unit.contains(&self.even_smoothing)
+            || !unit.contains(&self.odd_smoothing)
+            || !unit.contains(&self.min_signal_remaining)
+        {
+            return Err(FrontendError::InvalidNoiseReduction);
+        }
+
+        if self.enable_pcan
+            && (!self.pcan_strength.is_finite()
+                || !self.pcan_offset.is_finite()
+                || !(0..32).contains(&self.pcan_gain_bits))
+        {
+            return Err(FrontendError::InvalidPcanGainControl);
+        }
+
+        if self.enable_log && !(0..16).contains(&self.log_scale_shift) {
+            return Err(FrontendError::InvalidLogScale);
+        }
+
+        Ok(())
+    }
+
+    /// Convert to the bindgen representation
+    pub(crate) fn to_inner(self) -> bindings::FrontendConfig {
+        let mut config: bindings::FrontendConfig = Default::default();
+
+        config.window.size_ms = self.window_size_ms;
+        config.window.step_size_ms = self.window_step_ms;
+        config.filterbank.num_channels = self.num_channels;
+        config.filterbank.lower_band_limit = self.lower_band_limit;
+        config.filterbank.upper_band_limit = self.upper_band_limit;
+        config.filterbank.output_scale_shift = self.output_scale_shift;
+        config.noise_reduction.smoothing_bits = self.smoothing_bits;
+        config.noise_reduction.even_smoothing = self.even_smoothing;
+        config.noise_reduction.odd_smoothing = self.odd_smoothing;
+        config.noise_reduction.min_signal_remaining = self.min_signal_remaining;
+        config.pcan_gain_control.enable_pcan = self.enable_pcan as i32;
+        config.pcan_gain_control.strength = self.pcan_strength;
+        config.pcan_gain_control.offset = self.pcan_offset;
+        config.pcan_gain_control.gain_bits = self.pcan_gain_bits;
+        config.log_scale.enable_log = self.enable_log as i32;
+        config.log_scale.scale_shift = self.log_scale_shift;
+
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_config_is_valid() {
+        assert_eq!(FrontendConfig::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn window_samples() {
+        let config = FrontendConfig::new().sample_rate(8_000).window(25, 10);
+
+        assert_eq!(config.window_size_samples(), 200);
+        assert_eq!(config.window_step_samples(), 80);
+    }
+
+    #[test]
+    fn invalid_configs() {
+        let config = FrontendConfig::new();
+
+        assert_eq!(
+            config.sample_rate(0).validate(),
+            Err(FrontendError::InvalidSampleRate)
+        );
+        assert_eq!(
+            config.window(10, 20).validate(),
+            Err(FrontendError::InvalidWindow)
+        );
+        assert_eq!(
+            config.num_channels(0).validate(),
+            Err(FrontendError::InvalidChannelCount)
+        );
+        assert_eq!(
+            config.sample_rate(8_000).validate(),
+            Err(FrontendError::InvalidBandLimits)
+        );
+        assert_eq!(
+            config.noise_reduction(10, 1.5, 0.06, 0.05).validate(),
+            Err(FrontendError::InvalidNoiseReduction)
+        );
+        assert_eq!(
+            config.pcan_gain_control(f32::NAN, 80.0, 21).validate(),
+            Err(FrontendError::InvalidPcanGainControl)
+        );
+        assert_eq!(
+            config.log_scale(-1).validate(),
+            Err(FrontendError::InvalidLogScale)
+        );
+    }
+}
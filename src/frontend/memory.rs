@@ -0,0 +1,244 @@
+//! Memory allocation for the frontend state
+//!
+//! The frontend library allocates its buffers with `malloc` when the state
+//! is populated, and releases them with `free`. The build script renames
+//! the references to these symbols in the compiled library to
+//! `tflm_frontend_malloc` and `tflm_frontend_free`, which are defined here,
+//! so the C library's heap is never used.
+//!
+//! Whilst a caller-provided buffer is installed, allocations are taken from
+//! that buffer. Otherwise they are made with Rust's global allocator if the
+//! `alloc` feature is enabled, or fail if not. Each allocation is preceded
+//! by a header recording where it came from, so that `free` releases heap
+//! allocations whichever buffer is installed, and leaves allocations from a
+//! buffer alone.
+
+use core::ffi::c_void;
+use core::ptr;
+
+/// Alignment of allocations. This is the largest alignment required by any
+/// of the frontend state buffers.
+const ALIGN: usize = 8;
+
+/// Bytes before each allocation, which hold the size of a heap allocation,
+/// or `FROM_BUFFER`
+const HEADER: usize = if core::mem::size_of::<usize>() > ALIGN {
+    core::mem::size_of::<usize>()
+} else {
+    ALIGN
+};
+
+/// The header of an allocation from a caller-provided buffer. Heap
+/// allocations always include their header, so are never this size.
+const FROM_BUFFER: usize = 0;
+
+/// A bump allocator over a caller-provided buffer
+struct BufferAllocator {
+    start: *mut u8,
+    len: usize,
+    used: usize,
+}
+
+// The buffer installed for the duration of `with_buffer`, if any. It is
+// only accessed whilst `LOCK` is held: by `with_buffer`, and by
+// `tflm_frontend_malloc` when the frontend library is called from within
+// it.
+static mut BUFFER: Option<BufferAllocator> = None;
+
+/// Serialises populating the frontend state
+///
+/// Targets without atomic compare-and-swap, such as ARMv6-M, are single
+/// core. There, the caller must not populate two frontends concurrently,
+/// for example from an interrupt handler.
+#[cfg(target_has_atomic = "8")]
+static LOCK: core::sync::atomic::AtomicBool = core::sync::atomic::AtomicBool::new(false);
+
+#[cfg(target_has_atomic = "8")]
+struct LockGuard;
+
+#[cfg(target_has_atomic = "8")]
+impl LockGuard {
+    fn acquire() -> Self {
+        use core::sync::atomic::Ordering;
+
+        while LOCK
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            core::hint::spin_loop();
+        }
+        Self
+    }
+}
+
+#[cfg(target_has_atomic = "8")]
+impl Drop for LockGuard {
+    fn drop(&mut self) {
+        LOCK.store(false, core::sync::atomic::Ordering::Release);
+    }
+}
+
+/// Run `f` with allocations made by the frontend library redirected to
+/// `buffer`, or to the heap if `buffer` is `None`. Without the `alloc`
+/// feature, allocations fail if `buffer` is `None`.
+///
+/// Returns the result of `f` and the number of bytes used from `buffer`.
+pub(crate) fn with_buffer<R>(buffer: Option<&mut [u8]>, f: impl FnOnce() -> R) -> (R, usize) {
+    #[cfg(target_has_atomic = "8")]
+    let _guard = LockGuard::acquire();
+
+    unsafe {
+        BUFFER = buffer.map(|buffer| BufferAllocator {
+            start: buffer.as_mut_ptr(),
+            len: buffer.len(),
+            used: 0,
+        });
+    }
+
+    let result = f();
+
+    let used = unsafe {
+        let buffer = (*ptr::addr_of_mut!(BUFFER)).take();
+        buffer.map_or(0, |buffer| buffer.used)
+    };
+
+    (result, used)
+}
+
+#[no_mangle]
+extern "C" fn tflm_frontend_malloc(size: usize) -> *mut c_void {
+    let buffer = unsafe { &mut *ptr::addr_of_mut!(BUFFER) };
+
+    match buffer {
+        Some(buffer) => {
+            // Align the start of the allocation relative to its address,
+            // not to the start of the buffer. The header is a multiple of
+            // the alignment, so what follows it is aligned too.
+            let next = buffer.start as usize + buffer.used;
+            let padding = next.wrapping_neg() % ALIGN;
+            let needed = match size.checked_add(padding + HEADER) {
+                Some(needed) => needed,
+                None => return ptr::null_mut(),
+            };
+
+            if buffer.len - buffer.used < needed {
+                return ptr::null_mut();
+            }
+
+            unsafe {
+                let start = buffer.start.add(buffer.used + padding);
+                buffer.used += needed;
+
+                (start as *mut usize).write(FROM_BUFFER);
+                start.add(HEADER) as *mut c_void
+            }
+        }
+        None => heap_malloc(size),
+    }
+}
+
+#[no_mangle]
+extern "C" fn tflm_frontend_free(ptr: *mut c_void) {
+    if ptr.is_null() {
+        return;
+    }
+
+    // Allocations from a buffer are never freed, the buffer is reclaimed
+    // as a whole. See `Frontend::into_buffer`.
+    if !is_from_buffer(ptr) {
+        heap_free(ptr);
+    }
+}
+
+/// Whether `ptr`, returned by `tflm_frontend_malloc`, was allocated from a
+/// caller-provided buffer
+fn is_from_buffer(ptr: *mut c_void) -> bool {
+    unsafe { ((ptr as *const u8).sub(HEADER) as *const usize).read() == FROM_BUFFER }
+}
+
+/// Allocate `size` bytes from the global allocator, after a header that
+/// records the size of the allocation
+#[cfg(feature = "alloc")]
+fn heap_malloc(size: usize) -> *mut c_void {
+    use alloc::alloc::{alloc, Layout};
+
+    let layout = match size
+        .checked_add(HEADER)
+        .and_then(|total| Layout::from_size_align(total, ALIGN).ok())
+    {
+        Some(layout) => layout,
+        None => return ptr::null_mut(),
+    };
+
+    unsafe {
+        let start = alloc(layout);
+        if start.is_null() {
+            return ptr::null_mut();
+        }
+
+        (start as *mut usize).write(layout.size());
+        start.add(HEADER) as *mut c_void
+    }
+}
+
+/// Free an allocation made by `heap_malloc`
+#[cfg(feature = "alloc")]
+fn heap_free(ptr: *mut c_void) {
+    use alloc::alloc::{dealloc, Layout};
+
+    unsafe {
+        let start = (ptr as *mut u8).sub(HEADER);
+        let size = (start as *const usize).read();
+
+        dealloc(start, Layout::from_size_align_unchecked(size, ALIGN));
+    }
+}
+
+/// Without a global allocator there's no heap, so only caller-provided
+/// buffers can be used
+#[cfg(not(feature = "alloc"))]
+fn heap_malloc(_size: usize) -> *mut c_void {
+    ptr::null_mut()
+}
+
+/// Nothing is allocated on the heap without `alloc`, so there's nothing to
+/// free
+#[cfg(not(feature = "alloc"))]
+fn heap_free(_ptr: *mut c_void) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn free_heap_allocation_whilst_buffer_installed() {
+        let (heap, _) = with_buffer(None, || tflm_frontend_malloc(24));
+        assert!(!heap.is_null());
+        assert!(!is_from_buffer(heap));
+
+        let mut buffer = [0u8; 64];
+        let range = buffer.as_ptr_range();
+
+        let ((), used) = with_buffer(Some(&mut buffer[..]), || {
+            let allocation = tflm_frontend_malloc(16);
+            assert!(range.contains(&(allocation as *const u8)));
+            assert_eq!(allocation as usize % ALIGN, 0);
+            assert!(is_from_buffer(allocation));
+
+            // The heap allocation is released, and the one from the buffer
+            // is left alone
+            tflm_frontend_free(heap);
+            tflm_frontend_free(allocation);
+        });
+        assert!(used >= HEADER + 16);
+    }
+
+    #[test]
+    fn buffer_exhausted() {
+        let mut buffer = [0u8; 32];
+
+        let (allocation, _) = with_buffer(Some(&mut buffer[..]), || tflm_frontend_malloc(64));
+        assert!(allocation.is_null());
+    }
+}
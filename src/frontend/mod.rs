@@ -0,0 +1,343 @@
+//! Bindings for the audio "frontend" library for feature generation
+//!
+//! This API is in the 'experimental' directory in tensorflow. Therefore
+//! these bindings should also be considered to be experimental.
+//!
+//! See https://github.com/tensorflow/tensorflow/tree/master/tensorflow/lite/experimental/microfrontend/lib
+
+use core::slice;
+
+use crate::bindings;
+
+mod config;
+mod memory;
+mod scaling;
+mod window;
+pub use config::{FrontendConfig, FrontendError};
+pub use scaling::{Feature, FeatureScaling};
+pub use window::FeatureWindow;
+
+cpp! {{
+    #include "tensorflow/lite/experimental/microfrontend/lib/frontend.h"
+    #include "tensorflow/lite/experimental/microfrontend/lib/frontend_util.h"
+}}
+
+/// The result of feeding samples to a [`Frontend`](Frontend)
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FeatureStatus {
+    /// The number of samples read from the input
+    pub num_samples_read: usize,
+    /// Whether a window was completed, and the output written
+    pub produced: bool,
+}
+
+/// Bindings for the audio "frontend" library for feature generation
+pub struct Frontend {
+    state: bindings::FrontendState,
+    config: FrontendConfig,
+
+    // The caller-provided buffer holding the state and the number of bytes
+    // used from it, or `None` if the state lives on the heap
+    buffer: Option<(*mut [u8], usize)>,
+}
+
+// Frontend allocates memory on the heap or in a 'static buffer, therefore
+// the raw pointers that in contains are Send
+unsafe impl Send for Frontend {}
+
+impl Drop for Frontend {
+    fn drop(&mut self) {
+        // Only the buffers of the state on the heap are released, those in
+        // a caller-provided buffer are left alone
+        self.free_state_contents();
+    }
+}
+
+impl Frontend {
+    /// Create new frontend state, using the default
+    /// [`FrontendConfig`](FrontendConfig) from the micro_speech example
+    ///
+    /// # Errors
+    ///
+    /// Returns `FrontendError::PopulateStateError` if TensorFlow fails to
+    /// populate the frontend state.
+    pub fn new() -> Result<Self, FrontendError> {
+        Self::with_config(FrontendConfig::default())
+    }
+
+    /// Create new frontend state from a [`FrontendConfig`](FrontendConfig)
+    ///
+    /// The buffers of the frontend state are allocated on the heap, and are
+    /// freed when the frontend is dropped. This requires the `alloc`
+    /// feature; without it, use [`with_buffer`](#method.with_buffer).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FrontendError`](FrontendError) if the configuration is
+    /// invalid, or `FrontendError::PopulateStateError` if TensorFlow fails
+    /// to populate the frontend state. This occours if the `alloc` feature
+    /// is disabled.
+    pub fn with_config(config: FrontendConfig) -> Result<Self, FrontendError> {
+        Self::populate(config, None)
+    }
+
+    /// Create new frontend state from a [`FrontendConfig`](FrontendConfig),
+    /// allocating the buffers of the frontend state from `buffer` instead
+    /// of the heap, so no global allocator is needed.
+    ///
+    /// Call [`buffer_used_bytes`](#method.buffer_used_bytes) to find how
+    /// much of the buffer is needed for a given configuration, and
+    /// [`into_buffer`](#method.into_buffer) to reuse the buffer once the
+    /// frontend is no longer needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FrontendError`](FrontendError) if the configuration is
+    /// invalid, or `FrontendError::PopulateStateError` if TensorFlow fails
+    /// to populate the frontend state. This occours if `buffer` is too
+    /// small.
+    pub fn with_buffer(
+        config: FrontendConfig,
+        buffer: &'static mut [u8],
+    ) -> Result<Self, FrontendError> {
+        Self::populate(config, Some(buffer))
+    }
+
+    fn populate(
+        config: FrontendConfig,
+        buffer: Option<&'static mut [u8]>,
+    ) -> Result<Self, FrontendError> {
+        config.validate()?;
+
+        let buffer = buffer.map(|buffer| buffer as *mut [u8]);
+
+        let mut state: bindings::FrontendState = Default::default();
+        let state_ref = &mut state;
+
+        let sample_rate = config.get_sample_rate();
+        let inner_config = config.to_inner();
+
+        let (result, used) = memory::with_buffer(buffer.map(|b| unsafe { &mut *b }), || {
+            cpp! (unsafe [
+                inner_config as "FrontendConfig",
+                state_ref as "FrontendState*",
+                sample_rate as "int"
+            ] -> u32 as "int" {
+                return FrontendPopulateState(&inner_config,
+                                             state_ref,
+                                             sample_rate);
+            })
+        });
+
+        let frontend = Self {
+            state,
+            config,
+            buffer: buffer.map(|buffer| (buffer, used)),
+        };
+
+        // On failure, dropping the frontend releases anything that was
+        // allocated on the heap before the failure
+        if result == 1 {
+            Ok(frontend)
+        } else {
+            Err(FrontendError::PopulateStateError)
+        }
+    }
+
+    /// Create a new frontend with the same configuration as this one. The
+    /// new frontend starts from a freshly reset state, and its buffers are
+    /// allocated on the heap.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FrontendError::PopulateStateError` if TensorFlow fails to
+    /// populate the frontend state.
+    pub fn try_clone(&self) -> Result<Self, FrontendError> {
+        Self::with_config(self.config)
+    }
+
+    /// Reset the frontend state, such as the noise estimates and any
+    /// partially filled window, for example between utterances.
+    pub fn reset(&mut self) {
+        let state_ref = &mut self.state;
+
+        cpp!(unsafe [state_ref as "FrontendState*"] {
+            FrontendReset(state_ref);
+        });
+    }
+
+    /// Returns the number of bytes of the caller-provided buffer used by
+    /// the frontend state, or `None` if the state was allocated on the
+    /// heap.
+    pub fn buffer_used_bytes(&self) -> Option<usize> {
+        self.buffer.map(|(_, used)| used)
+    }
+
+    /// Consumes the frontend, returning the caller-provided buffer its
+    /// state was allocated from so that it can be reused, or `None` if the
+    /// state was allocated on the heap.
+    pub fn into_buffer(self) -> Option<&'static mut [u8]> {
+        let buffer = self.buffer;
+
+        // The state is released before the buffer is handed back
+        drop(self);
+
+        buffer.map(|(buffer, _)| unsafe { &mut *buffer })
+    }
+
+    fn free_state_contents(&mut self) {
+        let state_ref = &mut self.state;
+
+        cpp!(unsafe [state_ref as "FrontendState*"] {
+            FrontendFreeStateContents(state_ref);
+        });
+    }
+
+    /// Returns the configuration this frontend was created with
+    pub fn config(&self) -> &FrontendConfig {
+        &self.config
+    }
+
+    /// Generates micro_features objects
+    ///
+    /// Reads samples from `input` until either a window is complete or the
+    /// input is exhausted. Samples that do not complete a window are kept
+    /// by the frontend until the next call. The raw output still needs to
+    /// be scaled before it is passed to a model. See
+    /// [`generate_features`](#method.generate_features).
+    ///
+    /// # Errors
+    ///
+    /// Returns `FrontendError::OutputLenMismatch` if `output` is not the
+    /// configured number of channels long. No samples are read in this
+    /// case.
+    pub fn generate_micro_features(
+        &mut self,
+        input: &[i16],
+        output: &mut [u16],
+    ) -> Result<FeatureStatus, FrontendError> {
+        self.check_output_len(output.len())?;
+
+        let (frontend_output, num_samples_read) = self.process_samples(input);
+
+        let produced = !frontend_output.is_empty();
+        if produced {
+            output.copy_from_slice(frontend_output);
+        }
+
+        Ok(FeatureStatus {
+            num_samples_read,
+            produced,
+        })
+    }
+
+    /// Generates micro_features objects, scaled into features of type `T`
+    /// that can be copied directly into a model's input tensor.
+    ///
+    /// Use [`FeatureScaling::MicroSpeech`](FeatureScaling::MicroSpeech)
+    /// for models trained with the micro_speech pipeline, or derive the
+    /// scaling from the input tensor:
+    ///
+    /// ```ignore
+    /// let scaling = FeatureScaling::from(interpreter.input_info(0).quantization);
+    /// frontend.generate_features::<i8>(audio_slice, &mut features, scaling)?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `FrontendError::OutputLenMismatch` if `output` is not the
+    /// configured number of channels long. No samples are read in this
+    /// case.
+    pub fn generate_features<T: Feature>(
+        &mut self,
+        input: &[i16],
+        output: &mut [T],
+        scaling: FeatureScaling,
+    ) -> Result<FeatureStatus, FrontendError> {
+        self.check_output_len(output.len())?;
+
+        let (frontend_output, num_samples_read) = self.process_samples(input);
+
+        for (out, &raw) in output.iter_mut().zip(frontend_output) {
+            *out = T::from_raw(raw, scaling);
+        }
+
+        Ok(FeatureStatus {
+            num_samples_read,
+            produced: !frontend_output.is_empty(),
+        })
+    }
+
+    /// Feeds all of `input` to the frontend, calling `f` with the raw
+    /// output of every complete window. Samples left over at the end are
+    /// kept by the frontend until the next call.
+    ///
+    /// Returns the number of windows completed.
+    pub fn for_each_slice<F>(&mut self, mut input: &[i16], mut f: F) -> usize
+    where
+        F: FnMut(&[u16]),
+    {
+        let mut produced = 0;
+
+        while !input.is_empty() {
+            let (frontend_output, num_samples_read) = self.process_samples(input);
+
+            if !frontend_output.is_empty() {
+                f(frontend_output);
+                produced += 1;
+            }
+
+            // Guard against the frontend making no progress
+            if num_samples_read == 0 {
+                break;
+            }
+            input = &input[num_samples_read..];
+        }
+
+        produced
+    }
+
+    fn check_output_len(&self, len: usize) -> Result<(), FrontendError> {
+        if len == self.config.get_num_channels() {
+            Ok(())
+        } else {
+            Err(FrontendError::OutputLenMismatch)
+        }
+    }
+
+    /// Feeds `input` to the frontend. Returns the raw output, which lives
+    /// in the frontend state, and the number of samples read
+    fn process_samples(&mut self, input: &[i16]) -> (&[u16], usize) {
+        let micro_features_state_ref = &mut self.state;
+
+        let len = input.len();
+        let input = input.as_ptr();
+
+        let mut num_samples_read = 0usize;
+        let num_samples_read_ref = &mut num_samples_read;
+
+        let frontend_output = cpp! (unsafe [
+            micro_features_state_ref as "FrontendState*",
+            input as "int16_t*",
+            len as "size_t",
+            num_samples_read_ref as "size_t*"
+        ] -> bindings::FrontendOutput as "FrontendOutput" {
+            return FrontendProcessSamples(
+                micro_features_state_ref,
+                input,
+                len,
+                num_samples_read_ref
+            );
+        });
+
+        // No window was completed, in which case `values` may be null
+        if frontend_output.size == 0 || frontend_output.values.is_null() {
+            return (&[], num_samples_read);
+        }
+
+        let frontend_output_slice =
+            unsafe { slice::from_raw_parts(frontend_output.values, frontend_output.size) };
+
+        (frontend_output_slice, num_samples_read)
+    }
+}
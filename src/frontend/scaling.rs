@@ -0,0 +1,130 @@
+//! Scaling of raw frontend output into model features
+//!
+//! The frontend produces log-scaled `u16` filterbank energies. Models
+//! expect these scaled to the range that was used in the training
+//! pipeline, and then quantized to the element type of the input tensor.
+//!
+//! See https://github.com/tensorflow/tflite-micro/blob/main/tensorflow/lite/micro/examples/micro_speech/micro_features/micro_features_generator.cc
+
+use crate::tensor::QuantizationParams;
+
+/// How raw frontend output is scaled into model features
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum FeatureScaling {
+    /// The formulas used by the micro_speech example. That is, `(x * 2550 +
+    /// 3328) / 6656` for `u8` features, `(x * 256 + 333) / 666 - 128` for
+    /// `i8` features and `x * 10 / 256` for `f32` features.
+    #[default]
+    MicroSpeech,
+    /// Quantize the `x * 10 / 256` float features using the given
+    /// parameters. `f32` features are never quantized.
+    Quantized(QuantizationParams),
+}
+
+impl From<QuantizationParams> for FeatureScaling {
+    /// Derive the scaling from the quantization parameters of the target
+    /// input tensor. Falls back to `MicroSpeech` if the tensor is not
+    /// quantized.
+    fn from(params: QuantizationParams) -> Self {
+        if params.is_quantized() {
+            FeatureScaling::Quantized(params)
+        } else {
+            FeatureScaling::MicroSpeech
+        }
+    }
+}
+
+/// Converts a raw frontend output value into the float feature used in
+/// the training pipeline
+fn raw_to_float(raw: u16) -> f32 {
+    raw as f32 * (10.0 / 256.0)
+}
+
+/// Quantizes a raw frontend output value, without clamping
+fn quantize(raw: u16, params: QuantizationParams) -> i32 {
+    let value = raw_to_float(raw) / params.scale;
+
+    // Round half away from zero, as `core` has no `f32::round`
+    let value = if value < 0.0 {
+        value - 0.5
+    } else {
+        value + 0.5
+    };
+
+    value as i32 + params.zero_point
+}
+
+/// Element types the frontend can produce features as
+pub trait Feature: Copy {
+    /// Scale a raw frontend output value into a feature
+    fn from_raw(raw: u16, scaling: FeatureScaling) -> Self;
+}
+
+impl Feature for u8 {
+    fn from_raw(raw: u16, scaling: FeatureScaling) -> Self {
+        let value = match scaling {
+            FeatureScaling::MicroSpeech => (raw as i32 * 2550 + 3328) / 6656,
+            FeatureScaling::Quantized(params) => quantize(raw, params),
+        };
+
+        value.clamp(u8::MIN as i32, u8::MAX as i32) as u8
+    }
+}
+
+impl Feature for i8 {
+    fn from_raw(raw: u16, scaling: FeatureScaling) -> Self {
+        let value = match scaling {
+            FeatureScaling::MicroSpeech => (raw as i32 * 256 + 333) / 666 - 128,
+            FeatureScaling::Quantized(params) => quantize(raw, params),
+        };
+
+        value.clamp(i8::MIN as i32, i8::MAX as i32) as i8
+    }
+}
+
+impl Feature for f32 {
+    fn from_raw(raw: u16, _scaling: FeatureScaling) -> Self {
+        raw_to_float(raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn micro_speech_scaling() {
+        assert_eq!(u8::from_raw(0, FeatureScaling::MicroSpeech), 0);
+        assert_eq!(u8::from_raw(666, FeatureScaling::MicroSpeech), 255);
+        assert_eq!(u8::from_raw(u16::MAX, FeatureScaling::MicroSpeech), 255);
+
+        assert_eq!(i8::from_raw(0, FeatureScaling::MicroSpeech), -128);
+        assert_eq!(i8::from_raw(u16::MAX, FeatureScaling::MicroSpeech), 127);
+
+        assert_eq!(f32::from_raw(256, FeatureScaling::MicroSpeech), 10.0);
+    }
+
+    #[test]
+    fn quantized_scaling() {
+        let scaling: FeatureScaling = QuantizationParams {
+            scale: 0.1,
+            zero_point: -128,
+        }
+        .into();
+
+        assert_eq!(i8::from_raw(0, scaling), -128);
+        assert_eq!(i8::from_raw(256, scaling), -28);
+        assert_eq!(u8::from_raw(256, scaling), 0);
+    }
+
+    #[test]
+    fn unquantized_falls_back() {
+        let scaling: FeatureScaling = QuantizationParams {
+            scale: 0.0,
+            zero_point: 0,
+        }
+        .into();
+
+        assert_eq!(scaling, FeatureScaling::MicroSpeech);
+    }
+}
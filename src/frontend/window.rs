@@ -0,0 +1,167 @@
+//! A rolling window of feature slices, for streaming audio
+//!
+//! This is the equivalent of the `FeatureProvider` in the micro_speech
+//! example. Audio may be pushed in chunks of any size, for example as it
+//! arrives from a DMA transfer, and the window always holds the most
+//! recent `SLICES` feature slices.
+
+use super::{Feature, FeatureScaling, Frontend, FrontendError};
+
+/// A rolling window of `SLICES` feature slices, each of `CHANNELS`
+/// features, generated by a [`Frontend`](Frontend)
+///
+/// # Usage
+///
+/// ```ignore
+/// let frontend = Frontend::new().unwrap();
+/// let mut window: FeatureWindow<49, 40> = FeatureWindow::new(frontend).unwrap();
+///
+/// // As audio arrives
+/// window.push_samples(&audio_chunk);
+///
+/// if window.is_full() && window.new_slices() > 0 {
+///     let mut features = [0u8; 49 * 40];
+///     window.copy_to(&mut features, FeatureScaling::MicroSpeech);
+///     window.reset_new_slices();
+///
+///     interpreter.input(0, &features).unwrap();
+///     interpreter.invoke().unwrap();
+/// }
+/// ```
+pub struct FeatureWindow<const SLICES: usize, const CHANNELS: usize> {
+    frontend: Frontend,
+
+    // Ring buffer of raw slices. `head` is the index of the oldest slice,
+    // which is the next to be overwritten
+    slices: [[u16; CHANNELS]; SLICES],
+    head: usize,
+
+    // Number of slices produced, saturating at `SLICES`
+    len: usize,
+    // Number of slices produced since the last call to `reset_new_slices`
+    new_slices: usize,
+}
+
+impl<const SLICES: usize, const CHANNELS: usize> FeatureWindow<SLICES, CHANNELS> {
+    /// Create a new, empty, feature window
+    ///
+    /// # Errors
+    ///
+    /// Returns `FrontendError::InvalidChannelCount` if the frontend does
+    /// not produce `CHANNELS` features per slice, or if `SLICES` is zero.
+    pub fn new(frontend: Frontend) -> Result<Self, FrontendError> {
+        if SLICES == 0 || frontend.config().get_num_channels() != CHANNELS {
+            return Err(FrontendError::InvalidChannelCount);
+        }
+
+        Ok(Self {
+            frontend,
+            slices: [[0; CHANNELS]; SLICES],
+            head: 0,
+            len: 0,
+            new_slices: 0,
+        })
+    }
+
+    /// Feed audio samples into the window. Samples that do not complete a
+    /// slice are kept by the frontend until the next call.
+    ///
+    /// Returns the number of new slices produced by these samples. If this
+    /// is more than `SLICES`, the oldest of them have already been
+    /// discarded.
+    pub fn push_samples(&mut self, samples: &[i16]) -> usize {
+        let Self {
+            frontend,
+            slices,
+            head,
+            len,
+            ..
+        } = self;
+
+        let produced = frontend.for_each_slice(samples, |output| {
+            slices[*head].copy_from_slice(output);
+            *head = (*head + 1) % SLICES;
+            *len = (*len + 1).min(SLICES);
+        });
+
+        self.new_slices = self.new_slices.saturating_add(produced);
+
+        produced
+    }
+
+    /// Returns the number of slices currently held, at most `SLICES`
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether no slices have been produced yet
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns whether the window holds `SLICES` slices
+    pub fn is_full(&self) -> bool {
+        self.len == SLICES
+    }
+
+    /// Returns the number of slices produced since the window was created,
+    /// or since the last call to
+    /// [`reset_new_slices`](#method.reset_new_slices)
+    pub fn new_slices(&self) -> usize {
+        self.new_slices
+    }
+
+    /// Reset the count of new slices, typically after running inference
+    pub fn reset_new_slices(&mut self) {
+        self.new_slices = 0;
+    }
+
+    /// Returns the raw frontend output for the `n`th slice, where slice 0
+    /// is the oldest. Returns `None` if there is no such slice.
+    pub fn slice(&self, n: usize) -> Option<&[u16; CHANNELS]> {
+        if n >= self.len {
+            return None;
+        }
+
+        // While filling, the oldest slice is at index 0
+        let oldest = if self.is_full() { self.head } else { 0 };
+
+        Some(&self.slices[(oldest + n) % SLICES])
+    }
+
+    /// Scales the window into `output`, oldest slice first, in the
+    /// row-major `[SLICES, CHANNELS]` layout expected by an input tensor.
+    /// Slices that have not been produced yet are filled with the scaled
+    /// value of silence, a raw output of zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `output` is not `SLICES * CHANNELS` long.
+    pub fn copy_to<T: Feature>(&self, output: &mut [T], scaling: FeatureScaling) {
+        assert_eq!(output.len(), SLICES * CHANNELS);
+
+        // Unfilled slices come first, so the newest slice is always last
+        let missing = SLICES - self.len;
+
+        for (n, out) in output.chunks_exact_mut(CHANNELS).enumerate() {
+            match n.checked_sub(missing).and_then(|n| self.slice(n)) {
+                Some(raw) => {
+                    for (out, &raw) in out.iter_mut().zip(raw) {
+                        *out = T::from_raw(raw, scaling);
+                    }
+                }
+                None => out.fill(T::from_raw(0, scaling)),
+            }
+        }
+    }
+
+    /// Returns a reference to the underlying frontend
+    pub fn frontend(&self) -> &Frontend {
+        &self.frontend
+    }
+
+    /// Returns the underlying frontend, consuming the window
+    pub fn into_frontend(self) -> Frontend {
+        self.frontend
+    }
+}
@@ -13,7 +13,9 @@ pub(crate) mod strlen {
         #define HASZERO(x) (((x)-ONES) & ~(x) & HIGHS)
     }}
 
-    // A strlen implementation
+    // A strlen implementation, used by `DebugLog`
+    #[cfg(not(miri))]
+    #[cfg_attr(feature = "minimal", allow(dead_code))]
     pub unsafe fn strlen(string: *const cty::c_char) -> usize {
         cpp! ([string as "char *"] -> usize as "size_t" {
             const char *s = string;
@@ -25,12 +27,56 @@ pub(crate) mod strlen {
             return s-a;
         })
     }
+
+    // Miri can't call into C++
+    #[cfg(miri)]
+    #[cfg_attr(feature = "minimal", allow(dead_code))]
+    pub unsafe fn strlen(string: *const cty::c_char) -> usize {
+        core::ffi::CStr::from_ptr(string).to_bytes().len()
+    }
+}
+
+/// Forwarding TFLM's debug log to an interpreter's error reporter, see
+/// `MicroInterpreterBuilder::error_reporter`
+pub(crate) mod error_reporter {
+    use core::ptr;
+    use core::sync::atomic::{AtomicPtr, Ordering};
+
+    // The reporter installed by `with_reporter`, as a `fn(&str)`, or null.
+    // Only atomic loads and stores are used, which every target has.
+    static REPORTER: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+    /// Run `f` with TFLM's debug log sent to `reporter`, if any, instead of
+    /// to `log` or `defmt`.
+    pub(crate) fn with_reporter<R>(reporter: Option<fn(&str)>, f: impl FnOnce() -> R) -> R {
+        let reporter = match reporter {
+            Some(reporter) => reporter,
+            None => return f(),
+        };
+
+        let previous = REPORTER.load(Ordering::Acquire);
+        REPORTER.store(reporter as *mut (), Ordering::Release);
+        let result = f();
+        REPORTER.store(previous, Ordering::Release);
+
+        result
+    }
+
+    /// The installed reporter, if any
+    #[cfg_attr(feature = "minimal", allow(dead_code))]
+    pub(crate) fn reporter() -> Option<fn(&str)> {
+        let reporter = REPORTER.load(Ordering::Acquire);
+
+        // Only `fn(&str)`s are stored
+        (!reporter.is_null())
+            .then(|| unsafe { core::mem::transmute::<*mut (), fn(&str)>(reporter) })
+    }
 }
 
 // private module
 mod tensorflow {
-    use core::slice;
-    use core::str;
+    #[cfg(not(feature = "minimal"))]
+    use core::{slice, str};
 
     #[allow(clippy::empty_loop)]
     #[no_mangle]
@@ -104,16 +150,69 @@ mod tensorflow {
     }}
 
     #[no_mangle]
-    // Repalcement for implementation in debug_log.cc
+    // Repalcement for implementation in debug_log.cc. Forwards to the
+    // interpreter's error reporter if there is one, or else to `log`
+    // and/or `defmt`, depending on which features are enabled
+    #[cfg(not(feature = "minimal"))]
+    #[allow(unused_variables)]
     pub extern "C" fn DebugLog(s: *const cty::c_char) {
         let slice = unsafe {
             let len = super::strlen::strlen(s);
             let ptr = s as *const u8;
-            slice::from_raw_parts(ptr, len as usize + 1)
+            slice::from_raw_parts(ptr, len)
         };
-        info!("{}", str::from_utf8(slice).unwrap().trim());
+        let message = str::from_utf8(slice).unwrap().trim();
+
+        if let Some(reporter) = super::error_reporter::reporter() {
+            if !message.is_empty() {
+                reporter(message);
+            }
+            return;
+        }
+
+        #[cfg(feature = "log")]
+        log::info!("{}", message);
+
+        #[cfg(feature = "defmt")]
+        defmt::info!("{=str}", message);
     }
 
+    #[no_mangle]
+    // With the `minimal` feature, the debug log is discarded so that
+    // neither it nor the formatting code is linked
+    #[cfg(feature = "minimal")]
+    pub extern "C" fn DebugLog(_s: *const cty::c_char) {}
+
+    // With the `minimal` feature, this replaces micro_error_reporter.cc,
+    // which isn't compiled. Reports are discarded rather than formatted
+    // and passed to `DebugLog`, so the formatting code isn't linked.
+    // `cpp_build` ignores `cfg`, so it's selected by the define that
+    // `minimal` sets instead.
+    cpp! {{
+        #ifdef TF_LITE_STRIP_ERROR_STRINGS
+        #include <new>
+        #include "tensorflow/lite/micro/tflite_bridge/micro_error_reporter.h"
+
+        namespace {
+            uint8_t micro_error_reporter_buffer[sizeof(tflite::MicroErrorReporter)];
+            tflite::MicroErrorReporter* error_reporter_ = nullptr;
+        }
+
+        namespace tflite {
+            ErrorReporter* GetMicroErrorReporter() {
+                if (error_reporter_ == nullptr) {
+                    error_reporter_ = new (micro_error_reporter_buffer) MicroErrorReporter();
+                }
+                return error_reporter_;
+            }
+
+            int MicroErrorReporter::Report(const char* format, va_list args) {
+                return 0;
+            }
+        }
+        #endif
+    }}
+
     // Underlying assert function for tensorflow to use
     #[no_mangle]
     pub extern "C" fn __assert_func(
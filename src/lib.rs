@@ -1,69 +1,106 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-// #[macro_use]
-// extern crate log;
-// #[macro_use]
-// extern crate cpp;
+#[macro_use]
+extern crate cpp;
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
 pub mod bindings;
-// mod frontend;
-// mod interop;
+#[cfg(feature = "frontend")]
+mod frontend;
+mod interop;
+// Unused since `tflite::MicroInterpreter` stopped taking an `ErrorReporter`
 // mod micro_error_reporter;
-// mod micro_interpreter;
-// mod micro_op_resolver;
-// mod model;
-// mod operators;
-// mod tensor;
-// pub use frontend::Frontend;
-// pub use micro_interpreter::MicroInterpreter;
-// pub use micro_op_resolver::{AllOpResolver, MutableOpResolver};
-// pub use model::Model;
+mod micro_interpreter;
+mod micro_op_resolver;
+mod model;
+mod operators;
+#[cfg(feature = "profiler")]
+mod profiler;
+mod tensor;
+#[cfg(feature = "frontend")]
+pub use frontend::{
+    Feature, FeatureScaling, FeatureStatus, FeatureWindow, Frontend, FrontendConfig, FrontendError,
+};
+pub use micro_interpreter::{
+    ActiveInterpreter, MemoryPlanner, MicroInterpreter, MicroInterpreterBuilder, SharedArena,
+};
+pub use micro_op_resolver::{AllOpResolver, MutableOpResolver};
+pub use model::Model;
+#[cfg(feature = "profiler")]
+pub use profiler::Profiler;
+pub use tensor::{
+    ElemTypeOf, ElementType, Pod, QuantizationParams, Tensor, TensorInfo, TensorView, TensorViewMut,
+};
 
-// /// Error type for tfmicro
-// #[derive(Clone, Copy, PartialEq, Debug)]
-// pub enum Error {
-//     /// The model failed verification checks
-//     InvalidModel,
-//     /// An error occoured when instantiating the interpreter
-//     InterpreterInitError,
-//     /// An error occoured when allocating tensors in the tensor arena
-//     AllocateTensorsError,
-//     /// The length of the supplied slice was different to expect
-//     InputDataLenMismatch,
-//     /// The element type of the underlying data is not implemented by this crate
-//     ElementTypeUnimplemented,
-//     /// An error occoured converting some raw string to UTF8
-//     Utf8Error,
-// }
+/// Error type for tfmicro
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Error {
+    /// The model failed verification checks
+    InvalidModel,
+    /// The interpreter options conflict with each other
+    InvalidOptions,
+    /// The tensor arena is too small to hold the interpreter
+    ArenaTooSmall,
+    /// The shared arena is in use by another interpreter
+    ArenaInUse,
+    /// An error occoured when instantiating the interpreter
+    InterpreterInitError,
+    /// An error occoured when allocating tensors in the tensor arena
+    AllocateTensorsError,
+    /// The length of the supplied slice was different to expect
+    InputDataLenMismatch,
+    /// There is no tensor with the given name
+    TensorNotFound,
+    /// The element type of the underlying data is not implemented by this crate
+    ElementTypeUnimplemented,
+    /// The element type requested does not match that of the tensor
+    ElementTypeMismatch,
+    /// The tensor's data is not a whole number of the requested elements
+    TensorDataLenMismatch,
+    /// TensorFlow gave a nullptr for the tensor's data
+    NullTensorData,
+    /// The tensor's data is not aligned for the requested element type
+    MisalignedTensorData,
+    /// The tensor's data holds values that aren't valid for the requested
+    /// element type, such as a NaN for `NotNan<f32>`
+    InvalidTensorData,
+    /// An error occoured converting some raw string to UTF8
+    Utf8Error,
+}
 
-// /// The status resulting from a TensorFlow operation
-// #[derive(Clone, Copy, PartialEq, Debug)]
-// pub enum Status {
-//     Ok,
-//     Error,
-//     DelegateError,
-//     ApplicationError,
-//     DelegateDataNotFound,
-//     DelegateDataWriteError,
-//     DelegateDataReadError,
-//     UnresolvedOps,
-//     Cancelled,
-// }
+/// The status resulting from a TensorFlow operation
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Status {
+    Ok,
+    Error,
+    DelegateError,
+    ApplicationError,
+    DelegateDataNotFound,
+    DelegateDataWriteError,
+    DelegateDataReadError,
+    UnresolvedOps,
+    Cancelled,
+}
 
-// impl From<bindings::TfLiteStatus> for Status {
-//     fn from(status: bindings::TfLiteStatus) -> Self {
-//         use Status::*;
+impl From<bindings::TfLiteStatus> for Status {
+    fn from(status: bindings::TfLiteStatus) -> Self {
+        use Status::*;
 
-//         match status {
-//             bindings::TfLiteStatus::kTfLiteOk => Ok,
-//             bindings::TfLiteStatus::kTfLiteError => Error,
-//             bindings::TfLiteStatus::kTfLiteDelegateError => DelegateError,
-//             bindings::TfLiteStatus::kTfLiteApplicationError => ApplicationError,
-//             bindings::TfLiteStatus::kTfLiteDelegateDataNotFound => DelegateDataNotFound,
-//             bindings::TfLiteStatus::kTfLiteDelegateDataWriteError => DelegateDataWriteError,
-//             bindings::TfLiteStatus::kTfLiteDelegateDataReadError => DelegateDataReadError,
-//             bindings::TfLiteStatus::kTfLiteUnresolvedOps => UnresolvedOps,
-//             bindings::TfLiteStatus::kTfLiteCancelled => Cancelled,
-//         }
-//     }
-// }
+        match status {
+            bindings::TfLiteStatus::kTfLiteOk => Ok,
+            bindings::TfLiteStatus::kTfLiteError => Error,
+            bindings::TfLiteStatus::kTfLiteDelegateError => DelegateError,
+            bindings::TfLiteStatus::kTfLiteApplicationError => ApplicationError,
+            bindings::TfLiteStatus::kTfLiteDelegateDataNotFound => DelegateDataNotFound,
+            bindings::TfLiteStatus::kTfLiteDelegateDataWriteError => DelegateDataWriteError,
+            bindings::TfLiteStatus::kTfLiteDelegateDataReadError => DelegateDataReadError,
+            bindings::TfLiteStatus::kTfLiteUnresolvedOps => UnresolvedOps,
+            bindings::TfLiteStatus::kTfLiteCancelled => Cancelled,
+            // Newer versions of TensorFlow add statuses, such as
+            // `kTfLiteOutputShapeNotKnown`
+            #[allow(unreachable_patterns)]
+            _ => Error,
+        }
+    }
+}
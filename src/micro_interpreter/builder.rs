@@ -0,0 +1,578 @@
+//! Options for creating a [`MicroInterpreter`](crate::MicroInterpreter)
+
+use core::convert::TryInto;
+use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
+use core::ptr::{self, NonNull};
+
+use managed::ManagedSlice;
+
+use super::{MicroInterpreter, SharedArena};
+use crate::bindings;
+use crate::bindings::{tflite, tflite_micro_rs};
+use crate::interop::error_reporter;
+use crate::model::Model;
+use crate::Error;
+
+#[cfg(feature = "profiler")]
+use crate::profiler::Profiler;
+
+/// How TFLM plans the memory of the tensors in the arena
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MemoryPlanner {
+    /// Reuse the memory of tensors that are no longer needed. This needs
+    /// the smallest arena.
+    Greedy,
+    /// Give every tensor its own memory, so that intermediate tensors can
+    /// be inspected after `invoke` with
+    /// [`MicroInterpreter::tensor`](crate::MicroInterpreter::tensor). This
+    /// needs a larger arena.
+    Linear,
+}
+
+/// Options for creating a [`MicroInterpreter`](crate::MicroInterpreter),
+/// from [`MicroInterpreter::builder`](crate::MicroInterpreter::builder)
+pub struct MicroInterpreterBuilder<'a> {
+    model: &'a Model,
+    resolver: &'a tflite_micro_rs::OpResolver,
+    tensor_arena: ManagedSlice<'a, u8>,
+    non_persistent_arena: Option<ManagedSlice<'a, u8>>,
+    shared_arena: Option<&'a SharedArena<'a>>,
+    resource_variables: usize,
+    preserve_all_tensors: bool,
+    memory_planner: Option<MemoryPlanner>,
+    max_subgraphs: Option<usize>,
+    error_reporter: Option<fn(&str)>,
+    // A `tflite::MicroProfilerInterface`, borrowed for 'a, or null
+    profiler: *mut cty::c_void,
+    _phantom: PhantomData<&'a mut ()>,
+}
+
+impl<'a> MicroInterpreterBuilder<'a> {
+    pub(super) fn new(
+        model: &'a Model,
+        resolver: &'a tflite_micro_rs::OpResolver,
+        tensor_arena: ManagedSlice<'a, u8>,
+    ) -> Self {
+        Self {
+            model,
+            resolver,
+            tensor_arena,
+            non_persistent_arena: None,
+            shared_arena: None,
+            resource_variables: 0,
+            preserve_all_tensors: false,
+            memory_planner: None,
+            max_subgraphs: None,
+            error_reporter: None,
+            profiler: ptr::null_mut(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Allocate the non-persistent memory, which holds the tensors used
+    /// during `invoke`, from `arena` instead of from the tensor arena.
+    /// The tensor arena then only holds persistent memory, such as the
+    /// interpreter itself and the tensors' metadata.
+    ///
+    /// This allows the activations to be placed in faster memory, such as
+    /// tightly coupled memory, and the rest elsewhere. See
+    /// [`MicroInterpreter::persistent_arena_used_bytes`](crate::MicroInterpreter::persistent_arena_used_bytes)
+    /// and
+    /// [`MicroInterpreter::non_persistent_arena_used_bytes`](crate::MicroInterpreter::non_persistent_arena_used_bytes)
+    /// to size each arena.
+    pub fn non_persistent_arena<'t: 'a, TArena>(mut self, arena: TArena) -> Self
+    where
+        TArena: Into<ManagedSlice<'t, u8>>,
+    {
+        self.non_persistent_arena = Some(arena.into());
+        self
+    }
+
+    /// Allocate the non-persistent memory from `shared_arena`, which other
+    /// interpreters may also use. The interpreter must then be
+    /// [activated](crate::MicroInterpreter::activate) to use it. This can't
+    /// be combined with a [non-persistent arena](#method.non_persistent_arena).
+    pub fn shared_arena(mut self, shared_arena: &'a SharedArena<'a>) -> Self {
+        self.shared_arena = Some(shared_arena);
+        self
+    }
+
+    /// Allocate space in the arena for `count` resource variables, used
+    /// by the `VAR_HANDLE`, `READ_VARIABLE` and `ASSIGN_VARIABLE`
+    /// operators. Defaults to none.
+    pub fn resource_variables(mut self, count: usize) -> Self {
+        self.resource_variables = count;
+        self
+    }
+
+    /// Keep every tensor in the arena after `invoke`, instead of reusing
+    /// their memory, so that intermediate tensors can be inspected with
+    /// [`MicroInterpreter::tensor`](crate::MicroInterpreter::tensor). This
+    /// selects the [`Linear`](MemoryPlanner::Linear) memory planner.
+    /// Defaults to `false`.
+    pub fn preserve_all_tensors(mut self, preserve_all_tensors: bool) -> Self {
+        self.preserve_all_tensors = preserve_all_tensors;
+        self
+    }
+
+    /// How to plan the memory of the tensors in the arena. Defaults to
+    /// [`Greedy`](MemoryPlanner::Greedy), unless all tensors are
+    /// preserved.
+    pub fn memory_planner(mut self, memory_planner: MemoryPlanner) -> Self {
+        self.memory_planner = Some(memory_planner);
+        self
+    }
+
+    /// Only allocate models with at most `count` subgraphs. TFLM allocates
+    /// the nodes and tensors of every subgraph, including those only run
+    /// by control flow operators such as `IF` and `WHILE`, when the
+    /// interpreter is built, and plans the tensors of all of them together.
+    /// This bounds the persistent memory a model can take, so that one with
+    /// unexpected subgraphs is refused rather than filling the arena.
+    /// Defaults to no limit.
+    pub fn max_subgraphs(mut self, count: usize) -> Self {
+        self.max_subgraphs = Some(count);
+        self
+    }
+
+    /// Send the errors TFLM reports whilst building and invoking this
+    /// interpreter, or reading its tensors, to `reporter` instead of to the
+    /// `log` or `defmt` debug log.
+    ///
+    /// The reporter is installed for all of TFLM while this interpreter
+    /// calls into it, so errors from other interpreters running at the
+    /// same time, on other threads, may also reach it. With the `minimal`
+    /// feature, errors are discarded and it is never called.
+    pub fn error_reporter(mut self, reporter: fn(&str)) -> Self {
+        self.error_reporter = Some(reporter);
+        self
+    }
+
+    /// Record the time taken by each operator in `profiler`, which is
+    /// borrowed for the lifetime of the interpreter.
+    #[cfg(feature = "profiler")]
+    pub fn profiler(mut self, profiler: &'a mut Profiler) -> Self {
+        self.profiler = profiler.as_interface();
+        self
+    }
+
+    /// Check the options against each other
+    fn validate(&self) -> Result<MemoryPlanner, Error> {
+        if self.non_persistent_arena.is_some() && self.shared_arena.is_some() {
+            return Err(Error::InvalidOptions);
+        }
+        if self
+            .max_subgraphs
+            .map_or(false, |max| self.model.subgraphs_len() > max)
+        {
+            return Err(Error::InvalidOptions);
+        }
+
+        match (self.preserve_all_tensors, self.memory_planner) {
+            (true, Some(MemoryPlanner::Greedy)) => Err(Error::InvalidOptions),
+            (true, _) => Ok(MemoryPlanner::Linear),
+            (false, planner) => Ok(planner.unwrap_or(MemoryPlanner::Greedy)),
+        }
+    }
+
+    /// Create the interpreter, and allocate its tensors.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidOptions` if the options conflict, such as
+    /// preserving all tensors with the `Greedy` memory planner, if there
+    /// are too many resource variables, or if the model has more than
+    /// [`max_subgraphs`](#method.max_subgraphs).
+    ///
+    /// Returns `Error::ArenaInUse` if the shared arena is in use by an
+    /// active interpreter.
+    ///
+    /// Returns `Error::ArenaTooSmall` if the arenas can't hold the C++
+    /// interpreter object, its allocator or its resource variables.
+    ///
+    /// Returns `Error::InterpreterInitError` if there is an error creating
+    /// the interpreter.
+    ///
+    /// Returns `Error::AllocateTensors` if there is error in the call to
+    /// `AllocateTensors`.
+    pub fn build(self) -> Result<MicroInterpreter<'a>, Error> {
+        let linear = self.validate()? == MemoryPlanner::Linear;
+        let resource_variables: i32 = self
+            .resource_variables
+            .try_into()
+            .or(Err(Error::InvalidOptions))?;
+
+        let model = self.model;
+        let resolver = self.resolver;
+        let profiler = self.profiler;
+        let error_reporter = self.error_reporter;
+        let mut tensor_arena = self.tensor_arena;
+        let mut non_persistent_arena = self.non_persistent_arena;
+        let shared_arena = self.shared_arena;
+
+        // Allocating the tensors would overwrite the active interpreter's data
+        if shared_arena.map_or(false, SharedArena::is_in_use) {
+            return Err(Error::ArenaInUse);
+        }
+
+        // Reserve aligned space for the C++ interpreter at the start of the
+        // arena. TFLM gets the rest. The arena's buffer doesn't move when
+        // `tensor_arena` is moved into the interpreter, whether it's
+        // borrowed or owned.
+        let (size, align) = interpreter_layout();
+        let arena_start = tensor_arena.as_mut_ptr();
+        let padding = arena_start.align_offset(align);
+        let interpreter_bytes = padding
+            .checked_add(size)
+            .filter(|&bytes| bytes <= tensor_arena.len())
+            .ok_or(Error::ArenaTooSmall)?;
+
+        let slot = unsafe { arena_start.add(padding) };
+        let tensor_arena_ptr = unsafe { arena_start.add(interpreter_bytes) };
+        let tensor_arena_size = tensor_arena.len() - interpreter_bytes;
+
+        // Null if the tensor arena is used for everything
+        let (non_persistent_ptr, non_persistent_size) =
+            match (&mut non_persistent_arena, shared_arena) {
+                (Some(arena), _) => (arena.as_mut_ptr(), arena.len()),
+                (None, Some(arena)) => (arena.as_mut_ptr(), arena.len()),
+                (None, None) => (ptr::null_mut(), 0),
+            };
+
+        // The allocator is allocated from the arenas themselves
+        let allocator = unsafe {
+            cpp! ([
+                tensor_arena_ptr as "uint8_t*",
+                tensor_arena_size as "size_t",
+                non_persistent_ptr as "uint8_t*",
+                non_persistent_size as "size_t",
+                linear as "bool"
+            ] -> *mut cty::c_void as "tflite_micro_rs::MicroAllocator*"
+              {
+                  return tflite_micro_rs::MicroAllocator::Create(tensor_arena_ptr,
+                                                                 tensor_arena_size,
+                                                                 non_persistent_ptr,
+                                                                 non_persistent_size,
+                                                                 linear);
+              })
+        };
+        let allocator = NonNull::new(allocator).ok_or(Error::ArenaTooSmall)?;
+        let allocator_ptr = allocator.as_ptr();
+
+        // Construct the interpreter in place. It is never moved from here.
+        let micro_interpreter = error_reporter::with_reporter(error_reporter, || unsafe {
+            cpp! ([
+                slot as "void*",
+                model as "const tflite::Model*",
+                resolver as "const tflite::MicroMutableOpResolver<128>*",
+                allocator_ptr as "tflite_micro_rs::MicroAllocator*",
+                resource_variables as "int32_t",
+                profiler as "tflite::MicroProfilerInterface*"
+            ] -> *mut tflite::MicroInterpreter as "tflite::MicroInterpreter*"
+              {
+                  // Resource variables are allocated from the arena too
+                  tflite::MicroResourceVariables* variables = nullptr;
+                  if (resource_variables > 0) {
+                      variables = tflite::MicroResourceVariables::Create(allocator_ptr,
+                                                                         resource_variables);
+                      if (variables == nullptr) {
+                          return nullptr;
+                      }
+                  }
+
+                  // Build an interpreter to run the model with. Errors are
+                  // reported through `DebugLog`, see interop.rs
+                  return new (slot) tflite::MicroInterpreter(model,
+                                                             *resolver,
+                                                             allocator_ptr,
+                                                             variables,
+                                                             profiler);
+              })
+        });
+
+        // From here on, dropping `interpreter` destroys the C++ interpreter
+        let mut interpreter = MicroInterpreter {
+            micro_interpreter: NonNull::new(micro_interpreter).ok_or(Error::ArenaTooSmall)?,
+            allocator,
+            tensor_arena: ManuallyDrop::new(tensor_arena),
+            non_persistent_arena: ManuallyDrop::new(non_persistent_arena),
+            shared_arena,
+            preserves_all_tensors: linear,
+            error_reporter,
+            interpreter_bytes,
+            model,
+            _phantom: PhantomData,
+        };
+
+        let interpreter_ptr = interpreter.micro_interpreter.as_ptr();
+
+        // Get status
+        let status = unsafe {
+            cpp! ([interpreter_ptr as "tflite::MicroInterpreter*"]
+                   -> bindings::TfLiteStatus as "TfLiteStatus" {
+                return interpreter_ptr->initialization_status();
+            })
+        };
+        if status != bindings::TfLiteStatus::kTfLiteOk {
+            return Err(Error::InterpreterInitError);
+        }
+
+        // Allocate tensors, using the shared arena meanwhile
+        if let Some(shared_arena) = shared_arena {
+            if !shared_arena.claim(interpreter.id()) {
+                return Err(Error::ArenaInUse);
+            }
+        }
+        let allocate_tensors_status =
+            error_reporter::with_reporter(error_reporter, || interpreter.allocate_tensors());
+        if let Some(shared_arena) = shared_arena {
+            shared_arena.release();
+        }
+        if allocate_tensors_status != bindings::TfLiteStatus::kTfLiteOk {
+            return Err(Error::AllocateTensorsError);
+        }
+
+        Ok(interpreter)
+    }
+}
+
+/// The size and alignment of `tflite::MicroInterpreter`, as compiled. The
+/// bindings' opaque type may not match, as bindgen doesn't see every flag
+/// TFLM is compiled with.
+fn interpreter_layout() -> (usize, usize) {
+    unsafe {
+        cpp!([] -> [usize; 2] as "std::array<size_t, 2>" {
+            return {sizeof(tflite::MicroInterpreter), alignof(tflite::MicroInterpreter)};
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::micro_op_resolver::{AllOpResolver, MutableOpResolver};
+
+    #[test]
+    fn conflicting_options() {
+        let model = include_bytes!("../../examples/models/hello_world.tflite");
+        let model = Model::from_buffer(&model[..]).unwrap();
+
+        let all_op_resolver = AllOpResolver::new();
+
+        let mut tensor_arena = [0u8; 4 * 1024];
+
+        let result = MicroInterpreter::builder(&model, &all_op_resolver, &mut tensor_arena[..])
+            .preserve_all_tensors(true)
+            .memory_planner(MemoryPlanner::Greedy)
+            .build();
+        assert!(matches!(result, Err(Error::InvalidOptions)));
+    }
+
+    #[test]
+    fn preserve_all_tensors() {
+        let model = include_bytes!("../../examples/models/hello_world.tflite");
+        let model = Model::from_buffer(&model[..]).unwrap();
+
+        let all_op_resolver = AllOpResolver::new();
+
+        let mut greedy_arena = [0u8; 4 * 1024];
+        let mut linear_arena = [0u8; 4 * 1024];
+
+        let greedy = MicroInterpreter::builder(&model, &all_op_resolver, &mut greedy_arena[..])
+            .build()
+            .unwrap();
+        let mut linear = MicroInterpreter::builder(&model, &all_op_resolver, &mut linear_arena[..])
+            .preserve_all_tensors(true)
+            .build()
+            .unwrap();
+
+        // Without reuse, the tensors need more of the arena
+        assert!(linear.arena_used_bytes() > greedy.arena_used_bytes());
+
+        let x = 1.0f32;
+        linear.input(0, &[x]).unwrap();
+        linear.invoke().unwrap();
+
+        // hello_world quantizes its input into tensor 1, runs it through
+        // three FULLY_CONNECTED layers, the second with its output in
+        // tensor 7 and the third in tensor 0, then dequantizes the result
+        let quantized_input = linear.tensor(1).unwrap();
+        let q = quantized_input.quantization_params();
+        assert_eq!(
+            quantized_input.as_data::<i8>(),
+            &[((x / q.scale).round() as i32 + q.zero_point) as i8]
+        );
+
+        let hidden = linear.tensor(7).unwrap();
+        let q = hidden.quantization_params();
+        assert_eq!(hidden.dims(), &[1, 16]);
+        assert!(hidden
+            .as_data::<i8>()
+            .iter()
+            .all(|&h| h as i32 >= q.zero_point));
+
+        let quantized_output = linear.tensor(0).unwrap();
+        let q = quantized_output.quantization_params();
+        let y = (quantized_output.as_data::<i8>()[0] as i32 - q.zero_point) as f32 * q.scale;
+        assert!((y - linear.output(0).as_data::<f32>()[0]).abs() < 1e-6);
+
+        assert_eq!(linear.tensor(12).err(), Some(Error::TensorNotFound));
+
+        // Without preserving them, intermediate tensors may be overwritten
+        assert_eq!(greedy.tensor(1).err(), Some(Error::InvalidOptions));
+    }
+
+    #[test]
+    fn resource_variables() {
+        let model = include_bytes!("../../examples/models/hello_world.tflite");
+        let model = Model::from_buffer(&model[..]).unwrap();
+
+        let all_op_resolver = AllOpResolver::new();
+
+        let mut tensor_arena = [0u8; 4 * 1024];
+
+        let mut interpreter =
+            MicroInterpreter::builder(&model, &all_op_resolver, &mut tensor_arena[..])
+                .resource_variables(4)
+                .build()
+                .unwrap();
+        interpreter.input(0, &[1.0f32]).unwrap();
+        interpreter.invoke().unwrap();
+
+        let mut tensor_arena = [0u8; 4 * 1024];
+        let result = MicroInterpreter::builder(&model, &all_op_resolver, &mut tensor_arena[..])
+            .resource_variables(usize::MAX)
+            .build();
+        assert!(matches!(result, Err(Error::InvalidOptions)));
+    }
+
+    #[test]
+    fn max_subgraphs() {
+        let model = include_bytes!("../../examples/models/hello_world.tflite");
+        let model = Model::from_buffer(&model[..]).unwrap();
+
+        let all_op_resolver = AllOpResolver::new();
+
+        let mut tensor_arena = [0u8; 4 * 1024];
+        MicroInterpreter::builder(&model, &all_op_resolver, &mut tensor_arena[..])
+            .max_subgraphs(1)
+            .build()
+            .unwrap();
+
+        let mut tensor_arena = [0u8; 4 * 1024];
+        let result = MicroInterpreter::builder(&model, &all_op_resolver, &mut tensor_arena[..])
+            .max_subgraphs(0)
+            .build();
+        assert!(matches!(result, Err(Error::InvalidOptions)));
+    }
+
+    #[test]
+    #[cfg(not(feature = "minimal"))]
+    fn error_reporter() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        static REPORTS: AtomicUsize = AtomicUsize::new(0);
+
+        let model = include_bytes!("../../examples/models/hello_world.tflite");
+        let model = Model::from_buffer(&model[..]).unwrap();
+
+        // Without FULLY_CONNECTED, TFLM reports the missing operator
+        let resolver = MutableOpResolver::empty();
+
+        let mut tensor_arena = [0u8; 4 * 1024];
+        let result = MicroInterpreter::builder(&model, &resolver, &mut tensor_arena[..])
+            .error_reporter(|message| {
+                assert!(!message.is_empty());
+                REPORTS.fetch_add(1, Ordering::Relaxed);
+            })
+            .build();
+
+        assert!(result.is_err());
+        assert!(REPORTS.load(Ordering::Relaxed) > 0);
+    }
+
+    #[test]
+    fn non_persistent_arena() {
+        let model = include_bytes!("../../examples/models/hello_world.tflite");
+        let model = Model::from_buffer(&model[..]).unwrap();
+
+        let all_op_resolver = AllOpResolver::new();
+
+        let mut single_arena = [0u8; 4 * 1024];
+        let single =
+            MicroInterpreter::new(&model, &all_op_resolver, &mut single_arena[..]).unwrap();
+
+        let mut persistent_arena = [0u8; 4 * 1024];
+        let mut non_persistent_arena = [0u8; 1024];
+        let non_persistent_range = non_persistent_arena.as_ptr_range();
+        let mut split =
+            MicroInterpreter::builder(&model, &all_op_resolver, &mut persistent_arena[..])
+                .non_persistent_arena(&mut non_persistent_arena[..])
+                .build()
+                .unwrap();
+
+        // Each arena only holds its own kind of memory
+        assert!(split.non_persistent_arena_used_bytes() > 0);
+        assert!(split.non_persistent_arena_used_bytes() <= 1024);
+        assert_eq!(
+            split.arena_used_bytes(),
+            split.persistent_arena_used_bytes() + split.non_persistent_arena_used_bytes()
+        );
+        assert!(single.persistent_arena_used_bytes() < single.arena_used_bytes());
+
+        // The activations are in the non-persistent arena
+        split.input(0, &[1.0f32]).unwrap();
+        split.invoke().unwrap();
+        let output = split.output(0).as_bytes().as_ptr();
+        assert!(non_persistent_range.contains(&output));
+    }
+
+    #[test]
+    fn non_persistent_arena_too_small() {
+        let model = include_bytes!("../../examples/models/hello_world.tflite");
+        let model = Model::from_buffer(&model[..]).unwrap();
+
+        let all_op_resolver = AllOpResolver::new();
+
+        let mut persistent_arena = [0u8; 4 * 1024];
+        let mut non_persistent_arena = [0u8; 4];
+        let result = MicroInterpreter::builder(&model, &all_op_resolver, &mut persistent_arena[..])
+            .non_persistent_arena(&mut non_persistent_arena[..])
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    // Operators aren't profiled without error strings
+    #[cfg(all(feature = "profiler", not(feature = "minimal")))]
+    fn profiler() {
+        let model = include_bytes!("../../examples/models/hello_world.tflite");
+        let model = Model::from_buffer(&model[..]).unwrap();
+
+        let all_op_resolver = AllOpResolver::new();
+
+        let mut tensor_arena = [0u8; 4 * 1024];
+        let mut storage = core::mem::MaybeUninit::uninit();
+        let profiler = Profiler::new_in(&mut storage);
+        assert_eq!(profiler.event_count(), 0);
+        profiler.clear_events();
+
+        let mut interpreter =
+            MicroInterpreter::builder(&model, &all_op_resolver, &mut tensor_arena[..])
+                .profiler(&mut *profiler)
+                .build()
+                .unwrap();
+        interpreter.input(0, &[1.0f32]).unwrap();
+        interpreter.invoke().unwrap();
+        drop(interpreter);
+
+        // At least one event for each of the model's 5 operators
+        assert!(profiler.event_count() >= 5);
+        profiler.log();
+
+        profiler.clear_events();
+        assert_eq!(profiler.total_ticks(), 0);
+        assert_eq!(profiler.event_count(), 0);
+    }
+}
@@ -0,0 +1,926 @@
+//! A wrapper around the Tensorflow `MicroInterpreter`. Much like in the C
+//! API, this type takes a model, a set of working memory and the input
+//! tensors. It is then used to access the output tensors.
+//!
+//! See [`MicroInterpreter`](MicroInterpreter).
+//!
+//! # Usage
+//!
+//! ```rust
+//! # use tflite_micro::{
+//! #     MicroInterpreter, AllOpResolver, Model,
+//! # };
+//! // model
+//! let model = include_bytes!("../../examples/models/hello_world.tflite");
+//! let model = Model::from_buffer(&model[..]).unwrap();
+//!
+//! // resolver
+//! let all_op_resolver = AllOpResolver::new();
+//!
+//! // arena
+//! const TENSOR_ARENA_SIZE: usize = 4 * 1024;
+//! let mut tensor_arena: [u8; TENSOR_ARENA_SIZE] = [0; TENSOR_ARENA_SIZE];
+//!
+//! let _ = MicroInterpreter::new(
+//!     &model,
+//!     &all_op_resolver,
+//!     &mut tensor_arena[..],
+//! ).unwrap();
+//! ```
+//!
+//! Remember that once once you have instantiated the `MicroInterpreter`,
+//! the references you provided for `model`, `resolver` and `tensor_arena`
+//! must remain in scope. This is because the underlying C++ microinterpreter
+//! contains pointers to these objects.
+//!
+//! For example, the following will not compile:
+//!
+//! ```compile_fail
+//! # use tflite_micro::{
+//! #     MicroInterpreter, AllOpResolver, Model,
+//! # };
+//! let mut interpreter = {
+//!     let model = include_bytes!("../../examples/models/hello_world.tflite");
+//!     let model = Model::from_buffer(&model[..]).unwrap();
+//!
+//!     // ...
+//! # let all_op_resolver = AllOpResolver::new();
+//! # const TENSOR_ARENA_SIZE: usize = 4 * 1024;
+//! # let mut tensor_arena: [u8; TENSOR_ARENA_SIZE] = [0; TENSOR_ARENA_SIZE];
+//!
+//!     MicroInterpreter::new(
+//!         &model,
+//!         &all_op_resolver,
+//!         &mut tensor_arena[..],
+//!     ).unwrap()
+//! }; // Error [model, ..] dropped here whilst still borrowed
+//!
+//! // interpreter used here
+//! interpreter.input_info(0);
+//! ```
+//!
+//! The underlying C++ interpreter contains pointers to itself, so it can't
+//! be moved once it is constructed. It is constructed in place at the start
+//! of the tensor arena, which stays put for as long as the
+//! `MicroInterpreter` exists. The `MicroInterpreter` itself only points to
+//! it, so it can be moved freely. The arena must be large enough for the
+//! C++ interpreter object as well as the model's tensors.
+
+use core::convert::TryInto;
+use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
+use core::ptr::NonNull;
+
+use crate::micro_op_resolver::OpResolverRepr;
+use crate::tensor::{ElemTypeOf, Tensor, TensorInfo};
+use crate::Error;
+use crate::{model::Model, Status};
+use managed::ManagedSlice;
+
+use crate::bindings;
+use crate::bindings::tflite;
+use crate::interop::error_reporter;
+
+mod builder;
+mod shared_arena;
+pub use builder::{MemoryPlanner, MicroInterpreterBuilder};
+pub use shared_arena::{ActiveInterpreter, SharedArena};
+
+cpp! {{
+    #include <array>
+    #include <new>
+
+    #include "tensorflow/lite/micro/micro_interpreter.h"
+    #include "tensorflow/lite/micro/micro_mutable_op_resolver.h"
+    #include "tensorflow/lite/micro/kernels/micro_ops.h"
+    #include "tensorflow/lite/schema/schema_generated.h"
+
+    #include "tensorflow/lite/micro/arena_allocator/non_persistent_arena_buffer_allocator.h"
+    #include "tensorflow/lite/micro/arena_allocator/persistent_arena_buffer_allocator.h"
+    #include "tensorflow/lite/micro/arena_allocator/single_arena_buffer_allocator.h"
+    #include "tensorflow/lite/micro/memory_planner/greedy_memory_planner.h"
+    #include "tensorflow/lite/micro/memory_planner/linear_memory_planner.h"
+    #include "tensorflow/lite/micro/micro_allocator.h"
+    #include "tensorflow/lite/micro/micro_profiler_interface.h"
+    #include "tensorflow/lite/micro/micro_resource_variable.h"
+
+    namespace tflite_micro_rs {
+        // A `MicroAllocator` that keeps its buffer allocators, so that the
+        // usage of each arena can be reported. Otherwise it's created as
+        // `tflite::MicroAllocator::Create` creates one.
+        class MicroAllocator : public tflite::MicroAllocator {
+          public:
+            // Create an allocator in the persistent arena. If
+            // `non_persistent_arena` is null, the persistent arena is used
+            // for both. Returns null if the arenas are too small.
+            static MicroAllocator* Create(uint8_t* persistent_arena,
+                                          size_t persistent_arena_size,
+                                          uint8_t* non_persistent_arena,
+                                          size_t non_persistent_arena_size,
+                                          bool linear) {
+                tflite::IPersistentBufferAllocator* persistent;
+                tflite::INonPersistentBufferAllocator* non_persistent;
+
+                if (non_persistent_arena == nullptr) {
+                    tflite::SingleArenaBufferAllocator* single =
+                        tflite::SingleArenaBufferAllocator::Create(persistent_arena,
+                                                                   persistent_arena_size);
+                    persistent = single;
+                    non_persistent = single;
+                } else {
+                    // The persistent allocator is placed in its own arena
+                    tflite::PersistentArenaBufferAllocator tmp(persistent_arena,
+                                                              persistent_arena_size);
+                    persistent = Place<tflite::PersistentArenaBufferAllocator>(&tmp, tmp);
+                    if (persistent == nullptr) {
+                        return nullptr;
+                    }
+                    non_persistent = Place<tflite::NonPersistentArenaBufferAllocator>(
+                        persistent, non_persistent_arena, non_persistent_arena_size);
+                }
+                if (persistent == nullptr || non_persistent == nullptr) {
+                    return nullptr;
+                }
+
+                tflite::MicroMemoryPlanner* memory_planner;
+                if (linear) {
+                    memory_planner = Place<tflite::LinearMemoryPlanner>(persistent);
+                } else {
+                    memory_planner = Place<tflite::GreedyMemoryPlanner>(persistent);
+                }
+                if (memory_planner == nullptr) {
+                    return nullptr;
+                }
+
+                return Place<MicroAllocator>(persistent, persistent, non_persistent, memory_planner);
+            }
+
+            size_t PersistentUsedBytes() const {
+                return persistent_->GetPersistentUsedBytes();
+            }
+
+            size_t NonPersistentUsedBytes() const {
+                return non_persistent_->GetNonPersistentUsedBytes();
+            }
+
+          private:
+            MicroAllocator(tflite::IPersistentBufferAllocator* persistent,
+                           tflite::INonPersistentBufferAllocator* non_persistent,
+                           tflite::MicroMemoryPlanner* memory_planner)
+                : tflite::MicroAllocator(persistent, non_persistent, memory_planner),
+                  persistent_(persistent),
+                  non_persistent_(non_persistent) {}
+
+            // Construct a `T` in persistent memory
+            template <typename T, typename... Args>
+            static T* Place(tflite::IPersistentBufferAllocator* persistent,
+                            const Args&... args) {
+                uint8_t* buffer = persistent->AllocatePersistentBuffer(sizeof(T), alignof(T));
+                if (buffer == nullptr) {
+                    return nullptr;
+                }
+                return new (buffer) T(args...);
+            }
+
+            tflite::IPersistentBufferAllocator* persistent_;
+            tflite::INonPersistentBufferAllocator* non_persistent_;
+        };
+    }
+}}
+
+/// An interpreter for TensorFlow models
+///
+/// The C++ interpreter holds pointers to itself, so it must never move
+/// after it is constructed. It is constructed in place at the start of the
+/// tensor arena, which is borrowed or owned by the interpreter and so never
+/// moves either. This type only holds a pointer to it, and can be moved
+/// freely.
+pub struct MicroInterpreter<'a> {
+    // The C++ interpreter, constructed in place in `tensor_arena`
+    micro_interpreter: NonNull<tflite::MicroInterpreter>,
+
+    // The interpreter's `tflite_micro_rs::MicroAllocator`, in the arena.
+    // See builder.rs
+    allocator: NonNull<cty::c_void>,
+
+    // The C++ interpreter lives in, and points into, the arenas, so
+    // they're kept here. An owned arena would otherwise be dropped at the
+    // end of `new`. They're dropped after the C++ interpreter, see `Drop`.
+    tensor_arena: ManuallyDrop<ManagedSlice<'a, u8>>,
+    non_persistent_arena: ManuallyDrop<Option<ManagedSlice<'a, u8>>>,
+
+    // The non-persistent arena, if it's shared with other interpreters
+    shared_arena: Option<&'a SharedArena<'a>>,
+
+    // Whether every tensor has its own memory, see `tensor`
+    preserves_all_tensors: bool,
+
+    // Where TFLM's errors are sent whilst it runs this interpreter, see
+    // `MicroInterpreterBuilder::error_reporter`
+    error_reporter: Option<fn(&str)>,
+
+    // Bytes at the start of the arena used by the C++ interpreter itself
+    interpreter_bytes: usize,
+
+    // For the model's signatures
+    model: &'a Model,
+
+    // See https://doc.rust-lang.org/std/marker/struct.PhantomData.html#unused-lifetime-parameters
+    _phantom: PhantomData<&'a ()>,
+}
+
+impl Drop for MicroInterpreter<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            self.destroy_interpreter();
+            ManuallyDrop::drop(&mut self.tensor_arena);
+            ManuallyDrop::drop(&mut self.non_persistent_arena);
+        }
+    }
+}
+
+impl<'a> MicroInterpreter<'a> {
+    // From tensorflow source:
+    // tensorflow/lite/micro/micro_interpreter.h
+    //
+    // "The lifetime of the model, op resolver, tensor arena, and error
+    // reporter must be at least as long as that of the interpreter object,
+    // since the interpreter may need to access them at any time. This
+    // means that you should usually create them with the same scope as
+    // each other, for example having them all allocated on the stack as
+    // local variables through a top-level function.  The interpreter
+    // doesn't do any deallocation of any of the pointed-to objects,
+    // ownership remains with the caller."
+
+    /// Create a new micro_interpreter from a Model, a MicroOpResolver and a
+    /// tensor arena (scratchpad), with the default options. Use
+    /// [`builder`](#method.builder) to set other options.
+    ///
+    /// The C++ interpreter object itself is placed at the start of the
+    /// arena, see [`arena_used_bytes`](#method.arena_used_bytes).
+    ///
+    /// # Errors
+    ///
+    /// As for [`MicroInterpreterBuilder::build`](MicroInterpreterBuilder::build).
+    pub fn new<'m: 'a, 'r: 'a, 't: 'a, TArena, OpResolver>(
+        model: &'m Model,
+        resolver: &'r OpResolver,
+        tensor_arena: TArena,
+    ) -> Result<Self, Error>
+    where
+        OpResolver: OpResolverRepr,
+        TArena: Into<ManagedSlice<'t, u8>>,
+    {
+        Self::builder(model, resolver, tensor_arena).build()
+    }
+
+    /// Start building a micro_interpreter from a Model, a MicroOpResolver
+    /// and a tensor arena (scratchpad). See
+    /// [`MicroInterpreterBuilder`](MicroInterpreterBuilder) for the options.
+    ///
+    /// ```rust
+    /// # use tflite_micro::{MicroInterpreter, AllOpResolver, Model};
+    /// # let model = include_bytes!("../../examples/models/hello_world.tflite");
+    /// # let model = Model::from_buffer(&model[..]).unwrap();
+    /// # let all_op_resolver = AllOpResolver::new();
+    /// # let mut tensor_arena = [0u8; 4 * 1024];
+    /// let interpreter = MicroInterpreter::builder(&model, &all_op_resolver, &mut tensor_arena[..])
+    ///     .preserve_all_tensors(true)
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn builder<'m: 'a, 'r: 'a, 't: 'a, TArena, OpResolver>(
+        model: &'m Model,
+        resolver: &'r OpResolver,
+        tensor_arena: TArena,
+    ) -> MicroInterpreterBuilder<'a>
+    where
+        OpResolver: OpResolverRepr,
+        TArena: Into<ManagedSlice<'t, u8>>,
+    {
+        // The C++ interpreter keeps a reference to the resolver, which is
+        // borrowed for 'a
+        MicroInterpreterBuilder::new(model, resolver.as_inner(), tensor_arena.into())
+    }
+
+    fn allocate_tensors(&mut self) -> bindings::TfLiteStatus {
+        let interpreter = self.micro_interpreter.as_ptr();
+
+        unsafe {
+            cpp! ([interpreter as "tflite::MicroInterpreter*"]
+                   -> bindings::TfLiteStatus as "TfLiteStatus" {
+                return interpreter->AllocateTensors();
+            })
+        }
+    }
+
+    /// Run the destructor of the C++ interpreter, in place.
+    ///
+    /// # Safety
+    ///
+    /// Must be called exactly once, after which the C++ interpreter must
+    /// not be used.
+    unsafe fn destroy_interpreter(&mut self) {
+        let interpreter = self.micro_interpreter.as_ptr();
+
+        cpp!([interpreter as "tflite::MicroInterpreter*"] {
+            interpreter->~MicroInterpreter();
+        });
+    }
+
+    /// Consumes the interpreter, returning its tensor arena so that it can
+    /// be reused, for example with another model. A separate non-persistent
+    /// arena is dropped, see [`into_arenas`](#method.into_arenas).
+    ///
+    /// The contents of the arena are left as they are.
+    pub fn into_arena(self) -> ManagedSlice<'a, u8> {
+        self.into_arenas().0
+    }
+
+    /// Consumes the interpreter, returning its tensor arena and its
+    /// separate non-persistent arena, if it has one.
+    ///
+    /// The contents of the arenas are left as they are.
+    pub fn into_arenas(self) -> (ManagedSlice<'a, u8>, Option<ManagedSlice<'a, u8>>) {
+        let mut this = ManuallyDrop::new(self);
+
+        unsafe {
+            this.destroy_interpreter();
+            (
+                ManuallyDrop::take(&mut this.tensor_arena),
+                ManuallyDrop::take(&mut this.non_persistent_arena),
+            )
+        }
+    }
+
+    /// Take exclusive use of the interpreter's shared arena, which is
+    /// needed to set inputs, invoke and read outputs. The arena is released
+    /// when the returned guard is dropped. Interpreters without a shared
+    /// arena can always be activated, but don't need to be.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ArenaInUse` if another interpreter is active in the
+    /// shared arena.
+    pub fn activate(&mut self) -> Result<ActiveInterpreter<'_, 'a>, Error> {
+        ActiveInterpreter::new(self)
+    }
+
+    /// Identifies the interpreter to its shared arena
+    fn id(&self) -> usize {
+        self.micro_interpreter.as_ptr() as usize
+    }
+
+    /// Panics if the interpreter has a shared arena, and isn't active in it
+    fn assert_active(&self) {
+        if let Some(shared_arena) = self.shared_arena {
+            assert!(
+                shared_arena.is_owned_by(self.id()),
+                "An interpreter with a shared arena must be used through `MicroInterpreter::activate`"
+            );
+        }
+    }
+
+    /// Returns a [`TensorInfo`](crate::tensor::TensorInfo) that describes
+    /// the `n`th input tensor.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the underlying tensor cannot be represented by a
+    /// [`TensorInfo`](crate::tensor::TensorInfo).
+    pub fn input_info(&self, n: usize) -> TensorInfo {
+        // Panics if tensor cannot be repesented (eg. unimplemented type)
+        self.input_tensor(n).info()
+    }
+
+    /// Returns the number of input tensors
+    pub fn inputs_len(&self) -> usize {
+        let interpreter = self.micro_interpreter.as_ptr();
+        unsafe {
+            cpp!([interpreter as "tflite::MicroInterpreter*"]
+                  -> usize as "size_t" {
+                return interpreter->inputs_size();
+            })
+        }
+    }
+
+    /// Returns the number of output tensors
+    pub fn outputs_len(&self) -> usize {
+        let interpreter = self.micro_interpreter.as_ptr();
+        unsafe {
+            cpp!([interpreter as "tflite::MicroInterpreter*"]
+                  -> usize as "size_t" {
+                return interpreter->outputs_size();
+            })
+        }
+    }
+
+    /// Returns the index of the input tensor called `name` in the model.
+    /// See [`Model::input_index`](crate::Model::input_index).
+    pub fn input_index(&self, name: &str) -> Option<usize> {
+        self.model.input_index(name)
+    }
+
+    /// Returns the index of the output tensor called `name` in the model.
+    /// See [`Model::output_index`](crate::Model::output_index).
+    pub fn output_index(&self, name: &str) -> Option<usize> {
+        self.model.output_index(name)
+    }
+
+    /// Returns the name of the `n`th input tensor.
+    /// See [`Model::input_name`](crate::Model::input_name).
+    pub fn input_name(&self, n: usize) -> Option<&str> {
+        self.model.input_name(n)
+    }
+
+    /// Returns the name of the `n`th output tensor.
+    /// See [`Model::output_name`](crate::Model::output_name).
+    pub fn output_name(&self, n: usize) -> Option<&str> {
+        self.model.output_name(n)
+    }
+
+    /// Returns the index of the input called `name` in the model's
+    /// `SignatureDef` with the key `signature_key`. See
+    /// [`Model::signature_input_index`](crate::Model::signature_input_index).
+    pub fn signature_input_index(&self, signature_key: &str, name: &str) -> Option<usize> {
+        self.model.signature_input_index(signature_key, name)
+    }
+
+    /// Returns the index of the output called `name` in the model's
+    /// `SignatureDef` with the key `signature_key`. See
+    /// [`Model::signature_output_index`](crate::Model::signature_output_index).
+    pub fn signature_output_index(&self, signature_key: &str, name: &str) -> Option<usize> {
+        self.model.signature_output_index(signature_key, name)
+    }
+
+    fn input_tensor(&self, n: usize) -> &Tensor {
+        let interpreter = self.micro_interpreter.as_ptr();
+        unsafe {
+            // Call method on micro_interpreter
+            let inp = cpp!([
+                interpreter as "tflite::MicroInterpreter*",
+                n as "size_t"]
+                -> *mut bindings::TfLiteTensor as "TfLiteTensor*" {
+
+                return interpreter->input(n);
+            });
+
+            // Check result
+            assert!(!inp.is_null(), "Obtained nullptr from TensorFlow");
+
+            // From bindgen type to Rust type
+            Tensor::from_raw(inp)
+        }
+    }
+
+    /// Clones data into the `n`th input tensor.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InputDataLenMismatch` if the length of slice `data`
+    /// does not match the flat length of the `n`th input tensor.
+    ///
+    /// Returns `Error::ElementTypeMismatch` if `T` does not match the
+    /// element type of the `n`th input tensor.
+    ///
+    /// Returns an Error if the underlying tensor cannot be represented by a
+    /// [`TensorInfo`](crate::tensor::TensorInfo).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the attempt to get a pointer from TensorFlow returns a
+    /// nullptr. This can occour if the tensor index `n` is invalid.
+    ///
+    /// Panics if the interpreter has a shared arena, and isn't
+    /// [activated](#method.activate).
+    pub fn input<T: ElemTypeOf + core::clone::Clone>(
+        &mut self,
+        n: usize,
+        data: &[T],
+    ) -> Result<(), Error> {
+        self.assert_active();
+
+        let interpreter = self.micro_interpreter.as_ptr();
+        let input_tensor: &mut Tensor = unsafe {
+            // Call method on micro_interpreter
+            let inp = cpp!([
+                interpreter as "tflite::MicroInterpreter*",
+                n as "size_t"]
+                -> *mut bindings::TfLiteTensor as "TfLiteTensor*" {
+                return interpreter->input(n);
+            });
+
+            // Check result
+            assert!(!inp.is_null(), "Obtained nullptr from TensorFlow");
+
+            // From bindgen type to Rust type
+            Tensor::from_raw_mut(inp)
+        };
+
+        // Returns Err if tensor cannot be repesented (eg. unimplemented
+        // type)
+        let tensor_info: TensorInfo = input_tensor.inner().try_into()?;
+
+        // Length is the product of all dimensions
+        let tensor_len = tensor_info.dims.iter().product::<i32>();
+
+        if tensor_len != data.len().try_into().unwrap() {
+            Err(Error::InputDataLenMismatch)
+        } else {
+            input_tensor.try_as_data_mut()?.clone_from_slice(data);
+            Ok(())
+        }
+    }
+
+    /// Clones data into the input tensor called `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::TensorNotFound` if there is no input called `name`.
+    /// Otherwise as for [`input`](#method.input).
+    pub fn input_by_name<T: ElemTypeOf + core::clone::Clone>(
+        &mut self,
+        name: &str,
+        data: &[T],
+    ) -> Result<(), Error> {
+        let n = self.input_index(name).ok_or(Error::TensorNotFound)?;
+        self.input(n, data)
+    }
+
+    /// Runs the Tensorflow operation to transform input tensors to output
+    /// tensors
+    ///
+    /// # Errors
+    ///
+    /// Returns a TensorFlow [`Status`](crate::Status) if an error occours in
+    /// TensorFlow.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the interpreter has a shared arena, and isn't
+    /// [activated](#method.activate).
+    pub fn invoke(&mut self) -> Result<(), Status> {
+        self.assert_active();
+
+        let interpreter = self.micro_interpreter.as_ptr();
+
+        let status = error_reporter::with_reporter(self.error_reporter, || unsafe {
+            cpp!([interpreter as "tflite::MicroInterpreter*"]
+                  -> bindings::TfLiteStatus as "TfLiteStatus" {
+                return interpreter->Invoke();
+            })
+        });
+
+        // Return result
+        match status.into() {
+            Status::Ok => Ok(()),
+            e => Err(e),
+        }
+    }
+
+    /// Returns an immutable reference to the nth output tensor. The tensor
+    /// lives in the arena, so it's borrowed from the interpreter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the attempt to get a pointer from TensorFlow returns a
+    /// nullptr. This can occour if the tensor index `n` is invalid.
+    ///
+    /// Panics if the interpreter has a shared arena, and isn't
+    /// [activated](#method.activate).
+    pub fn output(&self, n: usize) -> &Tensor {
+        self.assert_active();
+        self.output_tensor(n)
+    }
+
+    /// The `n`th output tensor, without checking the interpreter is active
+    fn output_tensor(&self, n: usize) -> &Tensor {
+        let interpreter = self.micro_interpreter.as_ptr();
+        unsafe {
+            // Call method on micro_interpreter
+            let out = cpp!([
+                interpreter as "tflite::MicroInterpreter*",
+                n as "size_t"]
+                    -> *mut bindings::TfLiteTensor as "TfLiteTensor*" {
+                return interpreter->output(n);
+            });
+
+            // Check result
+            assert!(!out.is_null(), "Obtained nullptr from Tensorflow!");
+
+            // From bindgen type to Rust type
+            Tensor::from_raw(out)
+        }
+    }
+
+    /// Returns the tensor with `index` in the model's main subgraph, such as
+    /// an intermediate tensor after `invoke`. The interpreter must keep
+    /// every tensor, see
+    /// [`MicroInterpreterBuilder::preserve_all_tensors`](crate::MicroInterpreterBuilder::preserve_all_tensors).
+    ///
+    /// TFLM allocates the returned tensor's metadata from the persistent
+    /// part of the arena on each call, and doesn't free it until the
+    /// interpreter is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidOptions` if the interpreter reuses the memory
+    /// of tensors.
+    ///
+    /// Returns `Error::TensorNotFound` if there is no tensor with `index`.
+    ///
+    /// Returns `Error::ArenaTooSmall` if there's no space in the arena for
+    /// the tensor's metadata.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the interpreter has a shared arena, and isn't
+    /// [activated](#method.activate).
+    pub fn tensor(&self, index: usize) -> Result<&Tensor, Error> {
+        self.assert_active();
+
+        if !self.preserves_all_tensors {
+            return Err(Error::InvalidOptions);
+        }
+
+        let interpreter = self.micro_interpreter.as_ptr();
+        let model = self.model;
+        let mut found = false;
+        let found_ref = &mut found;
+
+        let tensor = error_reporter::with_reporter(self.error_reporter, || unsafe {
+            cpp!([
+                interpreter as "tflite::MicroInterpreter*",
+                model as "const tflite::Model*",
+                index as "size_t",
+                found_ref as "bool*"]
+                -> *mut bindings::TfLiteTensor as "TfLiteTensor*" {
+                auto tensors = model->subgraphs()->Get(0)->tensors();
+                if (tensors == nullptr || index >= tensors->size()) {
+                    return nullptr;
+                }
+
+                *found_ref = true;
+                return interpreter->GetTensor(index);
+            })
+        });
+
+        if !found {
+            Err(Error::TensorNotFound)
+        } else if tensor.is_null() {
+            Err(Error::ArenaTooSmall)
+        } else {
+            // From bindgen type to Rust type
+            Ok(unsafe { Tensor::from_raw(tensor) })
+        }
+    }
+
+    /// Returns an immutable reference to the output tensor called `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::TensorNotFound` if there is no output called `name`.
+    pub fn output_by_name(&self, name: &str) -> Result<&Tensor, Error> {
+        let n = self.output_index(name).ok_or(Error::TensorNotFound)?;
+        Ok(self.output(n))
+    }
+
+    /// Returns the actual number of bytes required for the arena. This
+    /// includes the C++ interpreter object at the start of the arena.
+    ///
+    /// With a separate non-persistent arena, this is the total of both
+    /// arenas.
+    pub fn arena_used_bytes(&self) -> usize {
+        let interpreter = self.micro_interpreter.as_ptr();
+        let used = unsafe {
+            cpp!([interpreter as "tflite::MicroInterpreter*"]
+                  -> usize as "size_t" {
+                return interpreter->arena_used_bytes();
+            })
+        };
+
+        self.interpreter_bytes + used
+    }
+
+    /// Returns the number of bytes of persistent memory used, which is in
+    /// the tensor arena. This includes the C++ interpreter object.
+    pub fn persistent_arena_used_bytes(&self) -> usize {
+        let allocator = self.allocator.as_ptr();
+        let used = unsafe {
+            cpp!([allocator as "const tflite_micro_rs::MicroAllocator*"]
+                  -> usize as "size_t" {
+                return allocator->PersistentUsedBytes();
+            })
+        };
+
+        self.interpreter_bytes + used
+    }
+
+    /// Returns the number of bytes of non-persistent memory used, which is
+    /// in the separate non-persistent arena if there is one, or else in the
+    /// tensor arena.
+    pub fn non_persistent_arena_used_bytes(&self) -> usize {
+        let allocator = self.allocator.as_ptr();
+        unsafe {
+            cpp!([allocator as "const tflite_micro_rs::MicroAllocator*"]
+                  -> usize as "size_t" {
+                return allocator->NonPersistentUsedBytes();
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::micro_op_resolver::AllOpResolver;
+    use crate::tensor::ElementType;
+
+    #[test]
+    fn new_interpreter_static_arena() {
+        // model
+        let model = include_bytes!("../../examples/models/hello_world.tflite");
+        let model = Model::from_buffer(&model[..]).unwrap();
+
+        // resolver
+        let all_op_resolver = AllOpResolver::new();
+
+        // arena
+        const TENSOR_ARENA_SIZE: usize = 4 * 1024;
+        let mut tensor_arena: [u8; TENSOR_ARENA_SIZE] = [0; TENSOR_ARENA_SIZE];
+
+        let _ = MicroInterpreter::new(&model, &all_op_resolver, &mut tensor_arena[..]).unwrap();
+    }
+
+    #[cfg(feature = "alloc")]
+    use alloc::{vec, vec::Vec};
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    fn new_interpreter_alloc_arena() {
+        // model
+        let model = include_bytes!("../../examples/models/hello_world.tflite");
+        let model = Model::from_buffer(&model[..]).unwrap();
+
+        // resolver
+        let all_op_resolver = AllOpResolver::new();
+
+        // arena
+        let tensor_arena: Vec<u8> = vec![0u8; 4 * 1024];
+
+        let mut interpreter =
+            MicroInterpreter::new(&model, &all_op_resolver, tensor_arena).unwrap();
+
+        // The owned arena must still be valid here
+        interpreter.input(0, &[1.0f32]).unwrap();
+        interpreter.invoke().unwrap();
+    }
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    fn into_arena() {
+        let model = include_bytes!("../../examples/models/hello_world.tflite");
+        let model = Model::from_buffer(&model[..]).unwrap();
+
+        let all_op_resolver = AllOpResolver::new();
+
+        let tensor_arena: Vec<u8> = vec![0u8; 4 * 1024];
+        let interpreter = MicroInterpreter::new(&model, &all_op_resolver, tensor_arena).unwrap();
+
+        // Reuse the arena for another interpreter
+        let tensor_arena = interpreter.into_arena();
+        assert_eq!(tensor_arena.len(), 4 * 1024);
+
+        let mut interpreter =
+            MicroInterpreter::new(&model, &all_op_resolver, tensor_arena).unwrap();
+        interpreter.input(0, &[1.0f32]).unwrap();
+        interpreter.invoke().unwrap();
+    }
+
+    #[test]
+    fn shared_resolver() {
+        let model = include_bytes!("../../examples/models/hello_world.tflite");
+        let model = Model::from_buffer(&model[..]).unwrap();
+
+        // One resolver, used by two interpreters
+        let all_op_resolver = AllOpResolver::new();
+
+        let mut tensor_arena_1 = [0u8; 4 * 1024];
+        let mut tensor_arena_2 = [0u8; 4 * 1024];
+
+        let mut interpreter_1 =
+            MicroInterpreter::new(&model, &all_op_resolver, &mut tensor_arena_1[..]).unwrap();
+        let mut interpreter_2 =
+            MicroInterpreter::new(&model, &all_op_resolver, &mut tensor_arena_2[..]).unwrap();
+
+        for interpreter in [&mut interpreter_1, &mut interpreter_2] {
+            interpreter.input(0, &[1.0f32]).unwrap();
+            interpreter.invoke().unwrap();
+        }
+
+        assert_eq!(
+            interpreter_1.output(0).as_data::<f32>(),
+            interpreter_2.output(0).as_data::<f32>()
+        );
+    }
+
+    #[test]
+    fn move_interpreter() {
+        let model = include_bytes!("../../examples/models/hello_world.tflite");
+        let model = Model::from_buffer(&model[..]).unwrap();
+
+        let all_op_resolver = AllOpResolver::new();
+
+        let mut tensor_arena = [0u8; 4 * 1024];
+
+        // Construct in one stack frame, and use after moving it out
+        fn new<'a>(
+            model: &'a Model,
+            resolver: &'a AllOpResolver,
+            tensor_arena: &'a mut [u8],
+        ) -> MicroInterpreter<'a> {
+            MicroInterpreter::new(model, resolver, tensor_arena).unwrap()
+        }
+        let interpreter = new(&model, &all_op_resolver, &mut tensor_arena[..]);
+        let mut interpreters = [interpreter];
+
+        interpreters[0].input(0, &[1.0f32]).unwrap();
+        interpreters[0].invoke().unwrap();
+    }
+
+    #[test]
+    fn arena_too_small() {
+        let model = include_bytes!("../../examples/models/hello_world.tflite");
+        let model = Model::from_buffer(&model[..]).unwrap();
+
+        let all_op_resolver = AllOpResolver::new();
+
+        let mut tensor_arena = [0u8; 4];
+
+        assert!(matches!(
+            MicroInterpreter::new(&model, &all_op_resolver, &mut tensor_arena[..]),
+            Err(Error::ArenaTooSmall)
+        ));
+    }
+
+    #[test]
+    fn tensors_by_name() {
+        let model = include_bytes!("../../examples/models/hello_world.tflite");
+        let model = Model::from_buffer(&model[..]).unwrap();
+
+        let all_op_resolver = AllOpResolver::new();
+
+        let mut tensor_arena = [0u8; 4 * 1024];
+
+        let mut interpreter =
+            MicroInterpreter::new(&model, &all_op_resolver, &mut tensor_arena[..]).unwrap();
+
+        assert_eq!(interpreter.inputs_len(), 1);
+        assert_eq!(interpreter.outputs_len(), 1);
+        assert_eq!(interpreter.input_index("dense_2_input"), Some(0));
+        assert_eq!(interpreter.input_index("Identity"), None);
+        assert_eq!(interpreter.output_index("Identity"), Some(0));
+
+        interpreter
+            .input_by_name("dense_2_input", &[1.0f32])
+            .unwrap();
+        assert_eq!(
+            interpreter.input_by_name("missing", &[1.0f32]),
+            Err(Error::TensorNotFound)
+        );
+        interpreter.invoke().unwrap();
+
+        assert_eq!(
+            interpreter
+                .output_by_name("Identity")
+                .unwrap()
+                .as_data::<f32>(),
+            interpreter.output(0).as_data::<f32>()
+        );
+        assert!(interpreter.output_by_name("missing").is_err());
+    }
+
+    #[test]
+    fn input_info() {
+        // model
+        let model = include_bytes!("../../examples/models/hello_world.tflite");
+        let model = Model::from_buffer(&model[..]).unwrap();
+
+        // resolver
+        let all_op_resolver = AllOpResolver::new();
+
+        // arena
+        const TENSOR_ARENA_SIZE: usize = 4 * 1024;
+        let mut tensor_arena: [u8; TENSOR_ARENA_SIZE] = [0; TENSOR_ARENA_SIZE];
+
+        let interpreter =
+            MicroInterpreter::new(&model, &all_op_resolver, &mut tensor_arena[..]).unwrap();
+
+        let info = interpreter.input_info(0);
+
+        // input tensor properties for hello_world example
+        assert_eq!(interpreter.input_name(0), Some("dense_2_input"));
+        assert_eq!(info.element_type, ElementType::Float32);
+        assert_eq!(info.dims, [1, 1]);
+    }
+}
@@ -0,0 +1,309 @@
+//! A non-persistent arena shared by several interpreters
+//!
+//! The non-persistent memory of an interpreter holds its input, output and
+//! intermediate tensors, which are only needed from setting the inputs
+//! until reading the outputs. Interpreters that never run at the same time
+//! can share it, and only need their own arena for persistent memory.
+//!
+//! ```rust
+//! # use tflite_micro::{AllOpResolver, MicroInterpreter, Model, SharedArena};
+//! # let model = include_bytes!("../../examples/models/hello_world.tflite");
+//! # let model = Model::from_buffer(&model[..]).unwrap();
+//! # let all_op_resolver = AllOpResolver::new();
+//! let mut activations = [0u8; 1024];
+//! let shared_arena = SharedArena::new(&mut activations[..]);
+//!
+//! let mut arena_1 = [0u8; 4 * 1024];
+//! let mut interpreter_1 = MicroInterpreter::builder(&model, &all_op_resolver, &mut arena_1[..])
+//!     .shared_arena(&shared_arena)
+//!     .build()
+//!     .unwrap();
+//! let mut arena_2 = [0u8; 4 * 1024];
+//! let mut interpreter_2 = MicroInterpreter::builder(&model, &all_op_resolver, &mut arena_2[..])
+//!     .shared_arena(&shared_arena)
+//!     .build()
+//!     .unwrap();
+//!
+//! // One at a time
+//! let mut active = interpreter_1.activate().unwrap();
+//! active.input(0, &[1.0f32]).unwrap();
+//! active.invoke().unwrap();
+//! let _y = active.output(0).as_data::<f32>()[0];
+//! drop(active);
+//!
+//! let _active = interpreter_2.activate().unwrap();
+//! // ...
+//! ```
+
+use core::cell::Cell;
+use core::ops::{Deref, DerefMut};
+
+use managed::ManagedSlice;
+
+use super::MicroInterpreter;
+use crate::Error;
+
+/// A non-persistent arena shared by several interpreters, which take turns
+/// to use it. See
+/// [`MicroInterpreterBuilder::shared_arena`](crate::MicroInterpreterBuilder::shared_arena).
+///
+/// The interpreters borrow the arena, and must be
+/// [activated](crate::MicroInterpreter::activate) to set inputs, invoke and
+/// read outputs. Only one may be active at a time. The inputs of an
+/// interpreter must be set again after another has been active, as the
+/// other overwrites them.
+pub struct SharedArena<'a> {
+    // Only accessed through `data`, by the interpreters' allocators
+    _arena: ManagedSlice<'a, u8>,
+    data: *mut u8,
+    len: usize,
+
+    // The active interpreter, by the address of its C++ object, or 0
+    owner: Cell<usize>,
+}
+
+impl<'a> SharedArena<'a> {
+    /// Create a shared arena from a borrowed or owned buffer
+    pub fn new<TArena>(arena: TArena) -> Self
+    where
+        TArena: Into<ManagedSlice<'a, u8>>,
+    {
+        let mut arena = arena.into();
+
+        Self {
+            data: arena.as_mut_ptr(),
+            len: arena.len(),
+            _arena: arena,
+            owner: Cell::new(0),
+        }
+    }
+
+    /// The size of the arena in bytes
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the arena has no space
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns whether an interpreter is using the arena
+    pub fn is_in_use(&self) -> bool {
+        self.owner.get() != 0
+    }
+
+    /// The arena, for an interpreter's allocator
+    pub(super) fn as_mut_ptr(&self) -> *mut u8 {
+        self.data
+    }
+
+    /// Mark the arena as used by the interpreter `id`. Returns `false` if
+    /// it's already in use, even by the same interpreter, as each claim is
+    /// released once.
+    pub(super) fn claim(&self, id: usize) -> bool {
+        if self.is_in_use() {
+            return false;
+        }
+
+        self.owner.set(id);
+        true
+    }
+
+    pub(super) fn release(&self) {
+        self.owner.set(0);
+    }
+
+    pub(super) fn is_owned_by(&self, id: usize) -> bool {
+        self.owner.get() == id
+    }
+}
+
+/// An interpreter with exclusive use of its shared arena, from
+/// [`MicroInterpreter::activate`](crate::MicroInterpreter::activate). The
+/// arena is released when this is dropped.
+pub struct ActiveInterpreter<'g, 'a> {
+    interpreter: &'g mut MicroInterpreter<'a>,
+}
+
+impl<'g, 'a> ActiveInterpreter<'g, 'a> {
+    pub(super) fn new(interpreter: &'g mut MicroInterpreter<'a>) -> Result<Self, Error> {
+        if let Some(shared_arena) = interpreter.shared_arena {
+            if !shared_arena.claim(interpreter.id()) {
+                return Err(Error::ArenaInUse);
+            }
+        }
+
+        Ok(Self { interpreter })
+    }
+}
+
+impl Drop for ActiveInterpreter<'_, '_> {
+    fn drop(&mut self) {
+        if let Some(shared_arena) = self.interpreter.shared_arena {
+            shared_arena.release();
+        }
+    }
+}
+
+impl<'a> Deref for ActiveInterpreter<'_, 'a> {
+    type Target = MicroInterpreter<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        self.interpreter
+    }
+}
+
+impl DerefMut for ActiveInterpreter<'_, '_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.interpreter
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::micro_op_resolver::AllOpResolver;
+    use crate::model::Model;
+
+    #[test]
+    fn shared_arena() {
+        let model = include_bytes!("../../examples/models/hello_world.tflite");
+        let model = Model::from_buffer(&model[..]).unwrap();
+
+        let all_op_resolver = AllOpResolver::new();
+
+        let mut activations = [0u8; 1024];
+        let shared_arena = SharedArena::new(&mut activations[..]);
+
+        let mut arena_1 = [0u8; 4 * 1024];
+        let mut interpreter_1 =
+            MicroInterpreter::builder(&model, &all_op_resolver, &mut arena_1[..])
+                .shared_arena(&shared_arena)
+                .build()
+                .unwrap();
+        let mut arena_2 = [0u8; 4 * 1024];
+        let mut interpreter_2 =
+            MicroInterpreter::builder(&model, &all_op_resolver, &mut arena_2[..])
+                .shared_arena(&shared_arena)
+                .build()
+                .unwrap();
+
+        let mut outputs = [0.0f32; 2];
+        for (interpreter, (x, y)) in [&mut interpreter_1, &mut interpreter_2]
+            .into_iter()
+            .zip([1.0f32, 2.0].iter().zip(&mut outputs))
+        {
+            let mut active = interpreter.activate().unwrap();
+            assert!(shared_arena.is_in_use());
+
+            active.input(0, &[*x]).unwrap();
+            active.invoke().unwrap();
+            *y = active.output(0).as_data::<f32>()[0];
+        }
+        assert!(!shared_arena.is_in_use());
+
+        // Both interpreters run the same model, so agree with one that
+        // has its own arenas
+        let mut arena = [0u8; 4 * 1024];
+        let mut reference =
+            MicroInterpreter::new(&model, &all_op_resolver, &mut arena[..]).unwrap();
+        for (x, y) in [1.0f32, 2.0].iter().zip(outputs) {
+            reference.input(0, &[*x]).unwrap();
+            reference.invoke().unwrap();
+            assert_eq!(reference.output(0).as_data::<f32>(), &[y]);
+        }
+    }
+
+    #[test]
+    fn one_active() {
+        let model = include_bytes!("../../examples/models/hello_world.tflite");
+        let model = Model::from_buffer(&model[..]).unwrap();
+
+        let all_op_resolver = AllOpResolver::new();
+
+        let mut activations = [0u8; 1024];
+        let shared_arena = SharedArena::new(&mut activations[..]);
+
+        let mut arena_1 = [0u8; 4 * 1024];
+        let mut interpreter_1 =
+            MicroInterpreter::builder(&model, &all_op_resolver, &mut arena_1[..])
+                .shared_arena(&shared_arena)
+                .build()
+                .unwrap();
+        let mut arena_2 = [0u8; 4 * 1024];
+        let mut interpreter_2 =
+            MicroInterpreter::builder(&model, &all_op_resolver, &mut arena_2[..])
+                .shared_arena(&shared_arena)
+                .build()
+                .unwrap();
+
+        let active = interpreter_1.activate().unwrap();
+        assert_eq!(interpreter_2.activate().err(), Some(Error::ArenaInUse));
+
+        // Nor can another interpreter be built in the arena meanwhile
+        let mut arena_3 = [0u8; 4 * 1024];
+        let result = MicroInterpreter::builder(&model, &all_op_resolver, &mut arena_3[..])
+            .shared_arena(&shared_arena)
+            .build();
+        assert!(matches!(result, Err(Error::ArenaInUse)));
+
+        drop(active);
+        assert!(interpreter_2.activate().is_ok());
+    }
+
+    #[test]
+    fn reactivate() {
+        let model = include_bytes!("../../examples/models/hello_world.tflite");
+        let model = Model::from_buffer(&model[..]).unwrap();
+
+        let all_op_resolver = AllOpResolver::new();
+
+        let mut activations = [0u8; 1024];
+        let shared_arena = SharedArena::new(&mut activations[..]);
+
+        let mut arena_1 = [0u8; 4 * 1024];
+        let mut interpreter_1 =
+            MicroInterpreter::builder(&model, &all_op_resolver, &mut arena_1[..])
+                .shared_arena(&shared_arena)
+                .build()
+                .unwrap();
+        let mut arena_2 = [0u8; 4 * 1024];
+        let mut interpreter_2 =
+            MicroInterpreter::builder(&model, &all_op_resolver, &mut arena_2[..])
+                .shared_arena(&shared_arena)
+                .build()
+                .unwrap();
+
+        // A second guard would release the arena under the first
+        let mut active = interpreter_1.activate().unwrap();
+        assert_eq!(active.activate().err(), Some(Error::ArenaInUse));
+
+        assert!(shared_arena.is_in_use());
+        assert_eq!(interpreter_2.activate().err(), Some(Error::ArenaInUse));
+        active.invoke().unwrap();
+
+        drop(active);
+        assert!(!shared_arena.is_in_use());
+    }
+
+    #[test]
+    #[should_panic(expected = "activate")]
+    fn invoke_inactive() {
+        let model = include_bytes!("../../examples/models/hello_world.tflite");
+        let model = Model::from_buffer(&model[..]).unwrap();
+
+        let all_op_resolver = AllOpResolver::new();
+
+        let mut activations = [0u8; 1024];
+        let shared_arena = SharedArena::new(&mut activations[..]);
+
+        let mut arena = [0u8; 4 * 1024];
+        let mut interpreter = MicroInterpreter::builder(&model, &all_op_resolver, &mut arena[..])
+            .shared_arena(&shared_arena)
+            .build()
+            .unwrap();
+
+        let _ = interpreter.invoke();
+    }
+}
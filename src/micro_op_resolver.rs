@@ -1,51 +1,65 @@
 //! Tensorflow Lite Op Resolvers
 //!
 
-use crate::bindings::tflite;
+use crate::bindings::tflite_micro_rs;
 
 use core::fmt;
 
 cpp! {{
     #include "tensorflow/lite/micro/micro_mutable_op_resolver.h"
-    // #include "tensorflow/lite/micro/kernels/all_ops_resolver.h"
 }}
 
-// AllOpsResolver has the same memory representation as
-// MicroMutableOpResolver<128>.
-//
-// That is:
-// class AllOpsResolver : public MicroMutableOpResolver<128> { ... }
-//
-// Thus we can cast between the two types.
-
-type OpResolverT = tflite::ops::micro::MicroMutableOpResolver<128>;
+// bindgen cannot instantiate the `MicroMutableOpResolver<128>` template
+// itself, so build/bind.rs instantiates it through a typedef.
+type OpResolverT = tflite_micro_rs::OpResolver;
 
 /// Marker trait for types that have the memory representation of a
 /// `OpResolver`
+///
+/// The interpreter keeps a reference to the op resolver, so resolvers are
+/// borrowed for the lifetime of the interpreter. One resolver may be shared
+/// between several interpreters.
 pub trait OpResolverRepr {
-    fn to_inner(self) -> OpResolverT;
+    fn as_inner(&self) -> &OpResolverT;
 }
 
-// /// An Op Resolver populated with all available operators
-// #[derive(Default)]
-// pub struct AllOpResolver(OpResolverT);
-// impl OpResolverRepr for AllOpResolver {
-//     fn to_inner(self) -> OpResolverT {
-//         self.0
-//     }
-// }
+/// An Op Resolver populated with all operators available in this crate
+///
+/// TensorFlow no longer provides an `AllOpsResolver`, so this is a
+/// [`MutableOpResolver`](MutableOpResolver) with every operator added whose
+/// `op-*` feature is enabled.
+pub struct AllOpResolver(MutableOpResolver);
+impl OpResolverRepr for AllOpResolver {
+    fn as_inner(&self) -> &OpResolverT {
+        self.0.as_inner()
+    }
+}
+impl fmt::Debug for AllOpResolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_fmt(format_args!("AllOpResolver (ops = {})", self.0.len()))
+    }
+}
+impl Default for AllOpResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 /// An Op Resolver that has no operators by default, but can be added by
 /// calling methods in a builder pattern
-#[derive(Default)]
 pub struct MutableOpResolver {
     pub(crate) inner: OpResolverT,
     capacity: usize,
     len: usize,
 }
 impl OpResolverRepr for MutableOpResolver {
-    fn to_inner(self) -> OpResolverT {
-        self.inner
+    fn as_inner(&self) -> &OpResolverT {
+        &self.inner
+    }
+}
+impl Default for MutableOpResolver {
+    fn default() -> Self {
+        Self::empty()
     }
 }
 impl fmt::Debug for MutableOpResolver {
@@ -54,24 +68,120 @@ impl fmt::Debug for MutableOpResolver {
     }
 }
 
-// impl AllOpResolver {
-//     /// Create a new Op Resolver, populated with all available
-//     /// operators
-//     pub fn new() -> Self {
-//         // The C++ compiler fills in the MicroMutableOpResolver with the
-//         // operators enumerated in AllOpsResolver
-//         let micro_op_resolver = unsafe {
-//             cpp!([] -> OpResolverT as "tflite::ops::micro::AllOpsResolver" {
-//                 // All ops
-//                 tflite::ops::micro::AllOpsResolver resolver;
-
-//                 return resolver;
-//             })
-//         };
-
-//         Self(micro_op_resolver)
-//     }
-// }
+impl AllOpResolver {
+    /// Create a new Op Resolver, populated with all available
+    /// operators. Only operators whose `op-*` feature is enabled are
+    /// available
+    pub fn new() -> Self {
+        let resolver = MutableOpResolver::empty();
+        #[cfg(feature = "op-fully-connected")]
+        let resolver = resolver.fully_connected();
+        #[cfg(feature = "op-pooling")]
+        let resolver = resolver.max_pool_2d();
+        #[cfg(feature = "op-softmax")]
+        let resolver = resolver.softmax();
+        #[cfg(feature = "op-logistic")]
+        let resolver = resolver.logistic();
+        #[cfg(feature = "op-svdf")]
+        let resolver = resolver.svdf();
+        #[cfg(feature = "op-conv")]
+        let resolver = resolver.conv_2d();
+        #[cfg(feature = "op-concatenation")]
+        let resolver = resolver.concatenation();
+        #[cfg(feature = "op-depthwise-conv")]
+        let resolver = resolver.depthwise_conv_2d();
+        #[cfg(feature = "op-pooling")]
+        let resolver = resolver.average_pool_2d();
+        #[cfg(feature = "op-elementwise")]
+        let resolver = resolver.abs();
+        #[cfg(feature = "op-elementwise")]
+        let resolver = resolver.sin();
+        #[cfg(feature = "op-elementwise")]
+        let resolver = resolver.cos();
+        #[cfg(feature = "op-elementwise")]
+        let resolver = resolver.log();
+        #[cfg(feature = "op-elementwise")]
+        let resolver = resolver.sqrt();
+        #[cfg(feature = "op-elementwise")]
+        let resolver = resolver.rsqrt();
+        #[cfg(feature = "op-elementwise")]
+        let resolver = resolver.square();
+        #[cfg(feature = "op-prelu")]
+        let resolver = resolver.prelu();
+        #[cfg(feature = "op-floor")]
+        let resolver = resolver.floor();
+        #[cfg(feature = "op-maximum-minimum")]
+        let resolver = resolver.maximum();
+        #[cfg(feature = "op-maximum-minimum")]
+        let resolver = resolver.minimum();
+        #[cfg(feature = "op-arg-min-max")]
+        let resolver = resolver.arg_max();
+        #[cfg(feature = "op-arg-min-max")]
+        let resolver = resolver.arg_min();
+        #[cfg(feature = "op-logical")]
+        let resolver = resolver.logical_or();
+        #[cfg(feature = "op-logical")]
+        let resolver = resolver.logical_and();
+        #[cfg(feature = "op-elementwise")]
+        let resolver = resolver.logical_not();
+        #[cfg(feature = "op-reshape")]
+        let resolver = resolver.reshape();
+        #[cfg(feature = "op-comparisons")]
+        let resolver = resolver.equal();
+        #[cfg(feature = "op-comparisons")]
+        let resolver = resolver.not_equal();
+        #[cfg(feature = "op-comparisons")]
+        let resolver = resolver.greater();
+        #[cfg(feature = "op-comparisons")]
+        let resolver = resolver.greater_equal();
+        #[cfg(feature = "op-comparisons")]
+        let resolver = resolver.less();
+        #[cfg(feature = "op-comparisons")]
+        let resolver = resolver.less_equal();
+        #[cfg(feature = "op-ceil")]
+        let resolver = resolver.ceil();
+        #[cfg(feature = "op-round")]
+        let resolver = resolver.round();
+        #[cfg(feature = "op-strided-slice")]
+        let resolver = resolver.strided_slice();
+        #[cfg(feature = "op-pack")]
+        let resolver = resolver.pack();
+        #[cfg(feature = "op-pad")]
+        let resolver = resolver.pad();
+        #[cfg(feature = "op-pad")]
+        let resolver = resolver.padv2();
+        #[cfg(feature = "op-split")]
+        let resolver = resolver.split();
+        #[cfg(feature = "op-unpack")]
+        let resolver = resolver.unpack();
+        #[cfg(feature = "op-neg")]
+        let resolver = resolver.neg();
+        #[cfg(feature = "op-add")]
+        let resolver = resolver.add();
+        #[cfg(feature = "op-mul")]
+        let resolver = resolver.mul();
+        #[cfg(feature = "op-sub")]
+        let resolver = resolver.sub();
+        #[cfg(feature = "op-quantize")]
+        let resolver = resolver.quantize();
+        #[cfg(feature = "op-dequantize")]
+        let resolver = resolver.dequantize();
+        #[cfg(feature = "op-activations")]
+        let resolver = resolver.relu();
+        #[cfg(feature = "op-activations")]
+        let resolver = resolver.relu6();
+        #[cfg(feature = "op-reduce")]
+        let resolver = resolver.mean();
+        #[cfg(feature = "op-resize-nearest-neighbor")]
+        let resolver = resolver.resize_nearest_neighbor();
+        #[cfg(feature = "op-l2-normalization")]
+        let resolver = resolver.l2_normalization();
+        #[cfg(feature = "op-tanh")]
+        let resolver = resolver.tanh();
+
+        Self(resolver)
+    }
+}
 
 impl MutableOpResolver {
     /// Check the number of operators is OK
@@ -97,9 +207,8 @@ impl MutableOpResolver {
 
     /// Create a new MutableOpResolver, initially empty
     pub fn empty() -> Self {
-        // Maximum number of registrations
-        //
-        // tensorflow/lite/micro/kernels/all_ops_resolver.h:L27
+        // Maximum number of registrations, matching the template parameter
+        // of `MicroMutableOpResolver`
         let tflite_registrations_max = 128;
 
         let micro_op_resolver = unsafe {
@@ -129,12 +238,17 @@ impl MutableOpResolver {
 mod tests {
     use super::*;
 
-    // #[test]
-    // fn all_ops_resolver() {
-    //     let _ = AllOpResolver::new();
-    // }
+    #[test]
+    fn all_ops_resolver() {
+        let _ = AllOpResolver::new();
+    }
 
     #[test]
+    #[cfg(all(
+        feature = "op-depthwise-conv",
+        feature = "op-fully-connected",
+        feature = "op-softmax"
+    ))]
     fn mutable_op_resolver() {
         let _ = MutableOpResolver::empty()
             .depthwise_conv_2d()
@@ -1,11 +1,55 @@
 //! TensorFlow model
 
+use core::{slice, str};
+
 use crate::bindings::tflite;
 use crate::Error;
 
+cpp! {{
+    namespace tflite_micro_rs {
+        // Compare a flatbuffer string with a Rust `&str`, which isn't null
+        // terminated
+        static bool NameEquals(const flatbuffers::String* s, const char* str, size_t len) {
+            if (s == nullptr || s->size() != len) {
+                return false;
+            }
+            for (size_t i = 0; i < len; i++) {
+                if (s->c_str()[i] != str[i]) {
+                    return false;
+                }
+            }
+            return true;
+        }
+
+        // The name of the `n`th input or output of the main subgraph, which
+        // are the interpreter's inputs and outputs. Returns null if there is
+        // no such tensor or its name was stripped.
+        static const flatbuffers::String* TensorName(const tflite::Model* model,
+                                                     bool output,
+                                                     size_t n) {
+            auto subgraphs = model->subgraphs();
+            if (subgraphs == nullptr || subgraphs->size() == 0) {
+                return nullptr;
+            }
+
+            auto subgraph = subgraphs->Get(0);
+            auto tensors = subgraph->tensors();
+            auto indices = output ? subgraph->outputs() : subgraph->inputs();
+            if (tensors == nullptr || indices == nullptr || n >= indices->size()) {
+                return nullptr;
+            }
+
+            auto tensor_index = indices->Get(n);
+            if (tensor_index < 0 || (uint32_t)tensor_index >= tensors->size()) {
+                return nullptr;
+            }
+            return tensors->Get(tensor_index)->name();
+        }
+    }
+}}
+
 /// A TensorFlow model
 #[repr(transparent)]
-#[derive(Default)]
 pub struct Model(tflite::Model);
 
 impl Model {
@@ -37,6 +81,204 @@ impl Model {
             Err(Error::InvalidModel)
         }
     }
+
+    /// Returns the number of subgraphs in the model. The first is the main
+    /// subgraph, which the interpreter runs. Others are run by control flow
+    /// operators such as `IF` and `WHILE`.
+    pub fn subgraphs_len(&self) -> usize {
+        let model = &self.0;
+
+        unsafe {
+            cpp!([model as "const tflite::Model*"] -> usize as "size_t" {
+                auto subgraphs = model->subgraphs();
+                return subgraphs == nullptr ? 0 : subgraphs->size();
+            })
+        }
+    }
+
+    /// Returns the index of the input tensor called `name`. This is the
+    /// index used by [`MicroInterpreter::input`](crate::MicroInterpreter::input).
+    ///
+    /// Returns `None` if there is no such input, including when the names
+    /// were stripped from the model.
+    pub fn input_index(&self, name: &str) -> Option<usize> {
+        self.tensor_index(name, false)
+    }
+
+    /// Returns the index of the output tensor called `name`. This is the
+    /// index used by [`MicroInterpreter::output`](crate::MicroInterpreter::output).
+    ///
+    /// Returns `None` if there is no such output, including when the names
+    /// were stripped from the model.
+    pub fn output_index(&self, name: &str) -> Option<usize> {
+        self.tensor_index(name, true)
+    }
+
+    /// Returns the index of the input called `name` in the model's
+    /// `SignatureDef` with the key `signature_key`. This is the index used
+    /// by [`MicroInterpreter::input`](crate::MicroInterpreter::input).
+    ///
+    /// Returns `None` if there is no such signature or input, or if the
+    /// signature isn't for the model's main subgraph.
+    pub fn signature_input_index(&self, signature_key: &str, name: &str) -> Option<usize> {
+        self.signature_index(signature_key, name, false)
+    }
+
+    /// Returns the index of the output called `name` in the model's
+    /// `SignatureDef` with the key `signature_key`. This is the index used
+    /// by [`MicroInterpreter::output`](crate::MicroInterpreter::output).
+    ///
+    /// Returns `None` if there is no such signature or output, or if the
+    /// signature isn't for the model's main subgraph.
+    pub fn signature_output_index(&self, signature_key: &str, name: &str) -> Option<usize> {
+        self.signature_index(signature_key, name, true)
+    }
+
+    /// Returns the name of the `n`th input tensor.
+    ///
+    /// Returns `None` if there is no such input, if its name was stripped
+    /// from the model or if the name isn't valid UTF-8.
+    pub fn input_name(&self, n: usize) -> Option<&str> {
+        self.tensor_name(n, false)
+    }
+
+    /// Returns the name of the `n`th output tensor.
+    ///
+    /// Returns `None` if there is no such output, if its name was stripped
+    /// from the model or if the name isn't valid UTF-8.
+    pub fn output_name(&self, n: usize) -> Option<&str> {
+        self.tensor_name(n, true)
+    }
+
+    // Tensor names are only kept in the flatbuffer, as TFLM's
+    // `TfLiteTensor` doesn't have them
+    fn tensor_name(&self, n: usize, output: bool) -> Option<&str> {
+        let model = &self.0;
+        let mut len = 0usize;
+        let len_ptr = &mut len as *mut usize;
+
+        let name = unsafe {
+            cpp!([
+                model as "const tflite::Model*",
+                n as "size_t",
+                output as "bool",
+                len_ptr as "size_t*"
+            ] -> *const u8 as "const char*" {
+                auto name = tflite_micro_rs::TensorName(model, output, n);
+                if (name == nullptr) {
+                    return nullptr;
+                }
+                *len_ptr = name->size();
+                return name->c_str();
+            })
+        };
+
+        if name.is_null() {
+            return None;
+        }
+
+        // The name lives in the model's buffer
+        let name = unsafe { slice::from_raw_parts(name, len) };
+        str::from_utf8(name).ok()
+    }
+
+    fn tensor_index(&self, name: &str, output: bool) -> Option<usize> {
+        let model = &self.0;
+        let name_len = name.len();
+        let name = name.as_ptr();
+
+        let index = unsafe {
+            cpp!([
+                model as "const tflite::Model*",
+                name as "const char*",
+                name_len as "size_t",
+                output as "bool"
+            ] -> i32 as "int32_t" {
+                auto subgraphs = model->subgraphs();
+                if (subgraphs == nullptr || subgraphs->size() == 0) {
+                    return -1;
+                }
+
+                auto subgraph = subgraphs->Get(0);
+                auto indices = output ? subgraph->outputs() : subgraph->inputs();
+                if (indices == nullptr) {
+                    return -1;
+                }
+
+                for (uint32_t i = 0; i < indices->size(); i++) {
+                    if (tflite_micro_rs::NameEquals(tflite_micro_rs::TensorName(model, output, i),
+                                                    name,
+                                                    name_len)) {
+                        return i;
+                    }
+                }
+
+                return -1;
+            })
+        };
+
+        usize::try_from(index).ok()
+    }
+
+    fn signature_index(&self, signature_key: &str, name: &str, output: bool) -> Option<usize> {
+        let model = &self.0;
+        let key_len = signature_key.len();
+        let key = signature_key.as_ptr();
+        let name_len = name.len();
+        let name = name.as_ptr();
+
+        let index = unsafe {
+            cpp!([
+                model as "const tflite::Model*",
+                key as "const char*",
+                key_len as "size_t",
+                name as "const char*",
+                name_len as "size_t",
+                output as "bool"
+            ] -> i32 as "int32_t" {
+                auto signatures = model->signature_defs();
+                auto subgraphs = model->subgraphs();
+                if (signatures == nullptr || subgraphs == nullptr || subgraphs->size() == 0) {
+                    return -1;
+                }
+
+                for (auto signature : *signatures) {
+                    if (!tflite_micro_rs::NameEquals(signature->signature_key(), key, key_len)) {
+                        continue;
+                    }
+
+                    // The interpreter's inputs and outputs are those of the
+                    // main subgraph
+                    if (signature->subgraph_index() != 0) {
+                        return -1;
+                    }
+
+                    auto tensor_maps = output ? signature->outputs() : signature->inputs();
+                    auto subgraph = subgraphs->Get(0);
+                    auto indices = output ? subgraph->outputs() : subgraph->inputs();
+                    if (tensor_maps == nullptr || indices == nullptr) {
+                        return -1;
+                    }
+
+                    for (auto tensor_map : *tensor_maps) {
+                        if (!tflite_micro_rs::NameEquals(tensor_map->name(), name, name_len)) {
+                            continue;
+                        }
+                        for (uint32_t i = 0; i < indices->size(); i++) {
+                            if ((uint32_t)indices->Get(i) == tensor_map->tensor_index()) {
+                                return i;
+                            }
+                        }
+                    }
+                    return -1;
+                }
+
+                return -1;
+            })
+        };
+
+        usize::try_from(index).ok()
+    }
 }
 
 #[cfg(test)]
@@ -51,6 +293,79 @@ mod tests {
         let _ = Model::from_buffer(&model[..]).unwrap();
     }
 
+    #[test]
+    fn no_signatures() {
+        let model = include_bytes!("../examples/models/hello_world.tflite");
+        let model = Model::from_buffer(&model[..]).unwrap();
+
+        // The example models are converted without signatures
+        assert_eq!(
+            model.signature_input_index("serving_default", "dense_2_input"),
+            None
+        );
+        assert_eq!(
+            model.signature_output_index("serving_default", "Identity"),
+            None
+        );
+    }
+
+    #[test]
+    fn tensor_names() {
+        let model = include_bytes!("../examples/models/hello_world.tflite");
+        let model = Model::from_buffer(&model[..]).unwrap();
+
+        assert_eq!(model.input_index("dense_2_input"), Some(0));
+        assert_eq!(model.input_index("Identity"), None);
+        assert_eq!(model.output_index("Identity"), Some(0));
+        assert_eq!(model.output_index("missing"), None);
+
+        assert_eq!(model.subgraphs_len(), 1);
+        assert_eq!(model.input_name(0), Some("dense_2_input"));
+        assert_eq!(model.input_name(1), None);
+        assert_eq!(model.output_name(0), Some("Identity"));
+        assert_eq!(model.output_name(1), None);
+    }
+
+    #[test]
+    fn signatures() {
+        // See make_signatures_model.py. The signature lists the inputs and
+        // outputs in the opposite order to the subgraph.
+        let model = include_bytes!("../examples/models/signatures.tflite");
+        let model = Model::from_buffer(&model[..]).unwrap();
+
+        assert_eq!(
+            model.signature_input_index("serving_default", "x_in"),
+            Some(0)
+        );
+        assert_eq!(
+            model.signature_input_index("serving_default", "y_in"),
+            Some(1)
+        );
+        assert_eq!(
+            model.signature_output_index("serving_default", "a_out"),
+            Some(0)
+        );
+        assert_eq!(
+            model.signature_output_index("serving_default", "b_out"),
+            Some(1)
+        );
+
+        // Names are looked up in the right map and signature
+        assert_eq!(
+            model.signature_input_index("serving_default", "a_out"),
+            None
+        );
+        assert_eq!(model.signature_output_index("serving_default", "x"), None);
+        assert_eq!(model.signature_input_index("other", "x_in"), None);
+
+        // Whilst the subgraph's own names are those of the tensors
+        assert_eq!(model.input_index("y"), Some(1));
+        assert_eq!(model.output_index("b"), Some(1));
+        assert_eq!(model.input_index("y_in"), None);
+        assert_eq!(model.input_name(1), Some("y"));
+        assert_eq!(model.output_name(0), Some("a"));
+    }
+
     #[test]
     #[should_panic]
     fn bad_model_from_buffer() {
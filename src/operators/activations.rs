@@ -0,0 +1,29 @@
+//! The RELU and RELU6 operators
+
+use crate::micro_op_resolver::MutableOpResolver;
+
+impl MutableOpResolver {
+    /// Use the RELU operator in this op resolver
+    pub fn relu(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddRelu();
+        });
+
+        self
+    }
+
+    /// Use the RELU6 operator in this op resolver
+    pub fn relu6(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddRelu6();
+        });
+
+        self
+    }
+}
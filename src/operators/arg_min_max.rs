@@ -0,0 +1,29 @@
+//! The ARG_MAX and ARG_MIN operators
+
+use crate::micro_op_resolver::MutableOpResolver;
+
+impl MutableOpResolver {
+    /// Use the ARG_MAX operator in this op resolver
+    pub fn arg_max(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddArgMax();
+        });
+
+        self
+    }
+
+    /// Use the ARG_MIN operator in this op resolver
+    pub fn arg_min(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddArgMin();
+        });
+
+        self
+    }
+}
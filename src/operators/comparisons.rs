@@ -0,0 +1,77 @@
+//! The comparison operators
+
+use crate::micro_op_resolver::MutableOpResolver;
+
+impl MutableOpResolver {
+    /// Use the EQUAL operator in this op resolver
+    pub fn equal(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddEqual();
+        });
+
+        self
+    }
+
+    /// Use the NOT_EQUAL operator in this op resolver
+    pub fn not_equal(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddNotEqual();
+        });
+
+        self
+    }
+
+    /// Use the GREATER operator in this op resolver
+    pub fn greater(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddGreater();
+        });
+
+        self
+    }
+
+    /// Use the GREATER_EQUAL operator in this op resolver
+    pub fn greater_equal(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddGreaterEqual();
+        });
+
+        self
+    }
+
+    /// Use the LESS operator in this op resolver
+    pub fn less(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddLess();
+        });
+
+        self
+    }
+
+    /// Use the LESS_EQUAL operator in this op resolver
+    pub fn less_equal(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddLessEqual();
+        });
+
+        self
+    }
+}
@@ -0,0 +1,101 @@
+//! The elementwise operators
+
+use crate::micro_op_resolver::MutableOpResolver;
+
+impl MutableOpResolver {
+    /// Use the ABS operator in this op resolver
+    pub fn abs(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddAbs();
+        });
+
+        self
+    }
+
+    /// Use the SIN operator in this op resolver
+    pub fn sin(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddSin();
+        });
+
+        self
+    }
+
+    /// Use the COS operator in this op resolver
+    pub fn cos(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddCos();
+        });
+
+        self
+    }
+
+    /// Use the LOG operator in this op resolver
+    pub fn log(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddLog();
+        });
+
+        self
+    }
+
+    /// Use the SQRT operator in this op resolver
+    pub fn sqrt(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddSqrt();
+        });
+
+        self
+    }
+
+    /// Use the RSQRT operator in this op resolver
+    pub fn rsqrt(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddRsqrt();
+        });
+
+        self
+    }
+
+    /// Use the SQUARE operator in this op resolver
+    pub fn square(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddSquare();
+        });
+
+        self
+    }
+
+    /// Use the LOGICAL_NOT operator in this op resolver
+    pub fn logical_not(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddLogicalNot();
+        });
+
+        self
+    }
+}
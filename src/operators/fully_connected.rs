@@ -0,0 +1,17 @@
+//! The FULLY_CONNECTED operator
+
+use crate::micro_op_resolver::MutableOpResolver;
+
+impl MutableOpResolver {
+    /// Use the FULLY_CONNECTED operator in this op resolver
+    pub fn fully_connected(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddFullyConnected();
+        });
+
+        self
+    }
+}
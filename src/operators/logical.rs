@@ -0,0 +1,29 @@
+//! The LOGICAL_OR and LOGICAL_AND operators
+
+use crate::micro_op_resolver::MutableOpResolver;
+
+impl MutableOpResolver {
+    /// Use the LOGICAL_OR operator in this op resolver
+    pub fn logical_or(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddLogicalOr();
+        });
+
+        self
+    }
+
+    /// Use the LOGICAL_AND operator in this op resolver
+    pub fn logical_and(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddLogicalAnd();
+        });
+
+        self
+    }
+}
@@ -0,0 +1,17 @@
+//! The LOGISTIC operator
+
+use crate::micro_op_resolver::MutableOpResolver;
+
+impl MutableOpResolver {
+    /// Use the LOGISTIC operator in this op resolver
+    pub fn logistic(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddLogistic();
+        });
+
+        self
+    }
+}
@@ -0,0 +1,29 @@
+//! The MAXIMUM and MINIMUM operators
+
+use crate::micro_op_resolver::MutableOpResolver;
+
+impl MutableOpResolver {
+    /// Use the MAXIMUM operator in this op resolver
+    pub fn maximum(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddMaximum();
+        });
+
+        self
+    }
+
+    /// Use the MINIMUM operator in this op resolver
+    pub fn minimum(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddMinimum();
+        });
+
+        self
+    }
+}
@@ -0,0 +1,96 @@
+//! Operators for Tensorflow micro
+//!
+//! Each module adds the operators implemented by one kernel, and is only
+//! compiled if its `op-*` feature is enabled. The build script compiles the
+//! same kernels, see build/kernels.rs.
+
+cpp! {{
+    #include "tensorflow/lite/micro/kernels/micro_ops.h"
+    #include "tensorflow/lite/micro/micro_mutable_op_resolver.h"
+    #include "tensorflow/lite/c/common.h"
+    #include "tensorflow/lite/core/api/flatbuffer_conversions.h"
+    #include "tensorflow/lite/kernels/internal/compatibility.h"
+    #include "tensorflow/lite/kernels/op_macros.h"
+    #include "tensorflow/lite/micro/compatibility.h"
+    #include "tensorflow/lite/micro/kernels/add.h"
+    #include "tensorflow/lite/micro/kernels/conv.h"
+    #include "tensorflow/lite/micro/kernels/depthwise_conv.h"
+    #include "tensorflow/lite/micro/kernels/ethosu.h"
+    #include "tensorflow/lite/micro/kernels/fully_connected.h"
+    #include "tensorflow/lite/micro/kernels/micro_ops.h"
+    #include "tensorflow/lite/micro/kernels/pooling.h"
+    #include "tensorflow/lite/micro/kernels/reduce.h"
+    #include "tensorflow/lite/micro/kernels/softmax.h"
+    #include "tensorflow/lite/micro/micro_log.h"
+    #include "tensorflow/lite/micro/micro_op_resolver.h"
+    #include "tensorflow/lite/schema/schema_generated.h"
+}}
+
+#[cfg(feature = "op-activations")]
+mod activations;
+#[cfg(feature = "op-add")]
+mod add;
+#[cfg(feature = "op-arg-min-max")]
+mod arg_min_max;
+#[cfg(feature = "op-ceil")]
+mod ceil;
+#[cfg(feature = "op-comparisons")]
+mod comparisons;
+#[cfg(feature = "op-concatenation")]
+mod concatenation;
+#[cfg(feature = "op-conv")]
+mod conv;
+#[cfg(feature = "op-depthwise-conv")]
+mod depthwise_conv;
+#[cfg(feature = "op-dequantize")]
+mod dequantize;
+#[cfg(feature = "op-elementwise")]
+mod elementwise;
+#[cfg(feature = "op-floor")]
+mod floor;
+#[cfg(feature = "op-fully-connected")]
+mod fully_connected;
+#[cfg(feature = "op-l2-normalization")]
+mod l2norm;
+#[cfg(feature = "op-logical")]
+mod logical;
+#[cfg(feature = "op-logistic")]
+mod logistic;
+#[cfg(feature = "op-maximum-minimum")]
+mod maximum_minimum;
+#[cfg(feature = "op-mul")]
+mod mul;
+#[cfg(feature = "op-neg")]
+mod neg;
+#[cfg(feature = "op-pack")]
+mod pack;
+#[cfg(feature = "op-pad")]
+mod pad;
+#[cfg(feature = "op-pooling")]
+mod pooling;
+#[cfg(feature = "op-prelu")]
+mod prelu;
+#[cfg(feature = "op-quantize")]
+mod quantize;
+#[cfg(feature = "op-reduce")]
+mod reduce;
+#[cfg(feature = "op-reshape")]
+mod reshape;
+#[cfg(feature = "op-resize-nearest-neighbor")]
+mod resize_nearest_neighbor;
+#[cfg(feature = "op-round")]
+mod round;
+#[cfg(feature = "op-softmax")]
+mod softmax;
+#[cfg(feature = "op-split")]
+mod split;
+#[cfg(feature = "op-strided-slice")]
+mod strided_slice;
+#[cfg(feature = "op-sub")]
+mod sub;
+#[cfg(feature = "op-svdf")]
+mod svdf;
+#[cfg(feature = "op-tanh")]
+mod tanh;
+#[cfg(feature = "op-unpack")]
+mod unpack;
@@ -0,0 +1,29 @@
+//! The PAD and PADV2 operators
+
+use crate::micro_op_resolver::MutableOpResolver;
+
+impl MutableOpResolver {
+    /// Use the PAD operator in this op resolver
+    pub fn pad(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddPad();
+        });
+
+        self
+    }
+
+    /// Use the PADV2 operator in this op resolver
+    pub fn padv2(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddPadV2();
+        });
+
+        self
+    }
+}
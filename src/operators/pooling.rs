@@ -0,0 +1,29 @@
+//! The MAX_POOL_2D and AVERAGE_POOL_2D operators
+
+use crate::micro_op_resolver::MutableOpResolver;
+
+impl MutableOpResolver {
+    /// Use the MAX_POOL_2D operator in this op resolver
+    pub fn max_pool_2d(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddMaxPool2D();
+        });
+
+        self
+    }
+
+    /// Use the AVERAGE_POOL_2D operator in this op resolver
+    pub fn average_pool_2d(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddAveragePool2D();
+        });
+
+        self
+    }
+}
@@ -0,0 +1,17 @@
+//! The STRIDED_SLICE operator
+
+use crate::micro_op_resolver::MutableOpResolver;
+
+impl MutableOpResolver {
+    /// Use the STRIDED_SLICE operator in this op resolver
+    pub fn strided_slice(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddStridedSlice();
+        });
+
+        self
+    }
+}
@@ -0,0 +1,17 @@
+//! The UNPACK operator
+
+use crate::micro_op_resolver::MutableOpResolver;
+
+impl MutableOpResolver {
+    /// Use the UNPACK operator in this op resolver
+    pub fn unpack(mut self) -> Self {
+        self.check_then_inc_len();
+        let inner_ref = &mut self.inner;
+
+        cpp!(unsafe [inner_ref as "tflite::MicroMutableOpResolver<128>*"] {
+            inner_ref->AddUnpack();
+        });
+
+        self
+    }
+}
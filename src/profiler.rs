@@ -0,0 +1,136 @@
+//! TFLM's `MicroProfiler`, which records the time taken by each operator
+//!
+//! Pass a [`Profiler`](Profiler) to
+//! [`MicroInterpreterBuilder::profiler`](crate::MicroInterpreterBuilder::profiler).
+//! Times are measured in ticks of TFLM's `GetCurrentTimeTicks`.
+//!
+//! The profiler holds every event in a fixed size buffer, which is tens of
+//! kilobytes, so it's constructed in place rather than on the stack:
+//!
+//! ```rust
+//! # use core::mem::MaybeUninit;
+//! # use tflite_micro::Profiler;
+//! static mut PROFILER: MaybeUninit<Profiler> = MaybeUninit::uninit();
+//!
+//! let profiler = Profiler::new_in(unsafe { &mut *core::ptr::addr_of_mut!(PROFILER) });
+//! ```
+
+use core::mem::{self, MaybeUninit};
+
+use crate::bindings::tflite;
+
+cpp! {{
+    #include <array>
+    #include <new>
+
+    #include "tensorflow/lite/micro/micro_profiler.h"
+}}
+
+/// Records the time taken by each operator during `invoke`
+#[repr(transparent)]
+pub struct Profiler(tflite::MicroProfiler);
+
+impl Profiler {
+    /// Create a new profiler in `storage`, with no events recorded. The
+    /// profiler is never dropped, and `storage` may be reused for a new one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bindings' layout of `tflite::MicroProfiler` doesn't
+    /// match TFLM's.
+    pub fn new_in(storage: &mut MaybeUninit<Profiler>) -> &mut Profiler {
+        let (size, align) = unsafe {
+            cpp!([] -> [usize; 2] as "std::array<size_t, 2>" {
+                return {sizeof(tflite::MicroProfiler), alignof(tflite::MicroProfiler)};
+            })
+        };
+        assert!(
+            size == mem::size_of::<Profiler>() && align <= mem::align_of::<Profiler>(),
+            "The layout of tflite::MicroProfiler doesn't match the bindings"
+        );
+
+        let slot = storage.as_mut_ptr();
+        unsafe {
+            cpp!([slot as "void*"] {
+                new (slot) tflite::MicroProfiler();
+            });
+
+            &mut *slot
+        }
+    }
+
+    /// The total ticks of all the events recorded
+    pub fn total_ticks(&self) -> u32 {
+        let profiler = &self.0;
+        unsafe {
+            cpp!([profiler as "const tflite::MicroProfiler*"] -> u32 as "uint32_t" {
+                return profiler->GetTotalTicks();
+            })
+        }
+    }
+
+    /// Forget the events recorded so far
+    pub fn clear_events(&mut self) {
+        let profiler = &mut self.0;
+        unsafe {
+            cpp!([profiler as "tflite::MicroProfiler*"] {
+                profiler->ClearEvents();
+            })
+        }
+    }
+
+    /// Write each event to the debug log
+    pub fn log(&self) {
+        let profiler = &self.0;
+        unsafe {
+            cpp!([profiler as "const tflite::MicroProfiler*"] {
+                profiler->Log();
+            })
+        }
+    }
+
+    /// Write each event to the debug log, as CSV
+    pub fn log_csv(&self) {
+        let profiler = &self.0;
+        unsafe {
+            cpp!([profiler as "const tflite::MicroProfiler*"] {
+                profiler->LogCsv();
+            })
+        }
+    }
+
+    /// Write the total ticks of each operator to the debug log, as CSV
+    pub fn log_ticks_per_tag_csv(&mut self) {
+        let profiler = &mut self.0;
+        unsafe {
+            cpp!([profiler as "tflite::MicroProfiler*"] {
+                profiler->LogTicksPerTagCsv();
+            })
+        }
+    }
+
+    /// The profiler, as the interface the interpreter takes
+    pub(crate) fn as_interface(&mut self) -> *mut cty::c_void {
+        let profiler = &mut self.0;
+        unsafe {
+            cpp!([profiler as "tflite::MicroProfiler*"] -> *mut cty::c_void as "void*" {
+                return static_cast<tflite::MicroProfilerInterface*>(profiler);
+            })
+        }
+    }
+
+    /// The number of events recorded since the last `clear_events`. This
+    /// records an event of its own, which later calls count.
+    #[cfg(test)]
+    pub(crate) fn event_count(&mut self) -> u32 {
+        let profiler = &mut self.0;
+        unsafe {
+            cpp!([profiler as "tflite::MicroProfiler*"] -> u32 as "uint32_t" {
+                // An event's handle is its index, so counts those before it
+                uint32_t count = profiler->BeginEvent("count");
+                profiler->EndEvent(count);
+                return count;
+            })
+        }
+    }
+}
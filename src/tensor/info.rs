@@ -2,45 +2,50 @@
 
 use core::convert::{TryFrom, TryInto};
 use core::fmt;
+use core::ptr;
 use core::slice;
-use core::str;
 
 use crate::bindings;
-use crate::interop;
 use crate::Error;
 
-use super::ElementType;
+use super::{ElementType, QuantizationParams};
 
 /// Metadata describing a tensor
 pub struct TensorInfo<'a> {
-    pub name: &'a str,
     pub element_type: ElementType,
     pub dims: &'a [i32],
+    pub quantization: QuantizationParams,
 }
 
 impl fmt::Debug for TensorInfo<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("TensorInfo")
-            .field("name", &self.name)
             .field("element_type", &self.element_type)
             .field("dims", &self.dims)
+            .field("quantization", &self.quantization)
             .finish()
     }
 }
 
+/// The shape of a tensor. Scalars may have no dims at all.
+pub(crate) fn dims(t: &bindings::TfLiteTensor) -> &[i32] {
+    if t.dims.is_null() {
+        return &[];
+    }
+
+    // `TfLiteIntArray` ends in a flexible array member, so the data is read
+    // through the raw pointer, not a reference to the header alone
+    unsafe {
+        let size = (*t.dims).size as usize;
+        let data = ptr::addr_of!((*t.dims).data) as *const i32;
+        slice::from_raw_parts(data, size)
+    }
+}
+
 impl<'a> TryFrom<&'a bindings::TfLiteTensor> for TensorInfo<'a> {
     type Error = Error;
 
     fn try_from(t: &'a bindings::TfLiteTensor) -> Result<Self, Self::Error> {
-        let name_as_slice = unsafe {
-            let len = interop::strlen::strlen(t.name);
-            let ptr = t.name as *const u8;
-            slice::from_raw_parts(ptr, len as usize)
-        };
-
-        // Parse name as utf8
-        let name = str::from_utf8(name_as_slice).or(Err(Error::Utf8Error))?;
-
         // Attempt to match type_ as a ElementType
         let element_type = t
             .type_
@@ -48,12 +53,50 @@ impl<'a> TryFrom<&'a bindings::TfLiteTensor> for TensorInfo<'a> {
             .or(Err(Error::ElementTypeUnimplemented))?;
 
         Ok(Self {
-            name,
             element_type,
-            dims: unsafe {
-                let dims = &*t.dims;
-                dims.data.as_slice(dims.size as usize)
-            },
+            dims: dims(t),
+            quantization: t.params.into(),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tensor::tests::MockTensor;
+
+    /// A `TfLiteIntArray` with its flexible array member filled in
+    #[repr(C)]
+    struct MockIntArray<const N: usize> {
+        size: cty::c_int,
+        data: [cty::c_int; N],
+    }
+
+    #[test]
+    fn info() {
+        let mut dims = MockIntArray {
+            size: 2,
+            data: [1, 3],
+        };
+
+        let mut mock = MockTensor::new([0.0; 3]);
+        let raw = mock.as_raw();
+        unsafe {
+            (*raw).dims = ptr::addr_of_mut!(dims).cast();
+        }
+
+        let info: TensorInfo = unsafe { &*raw }.try_into().unwrap();
+        assert_eq!(info.element_type, ElementType::Float32);
+        assert_eq!(info.dims, [1, 3]);
+    }
+
+    #[test]
+    fn info_scalar() {
+        // Scalars may have no dims
+        let mut mock = MockTensor::new([0.0]);
+        let info: TensorInfo = unsafe { &*mock.as_raw() }.try_into().unwrap();
+
+        assert_eq!(info.element_type, ElementType::Float32);
+        assert!(info.dims.is_empty());
+    }
+}
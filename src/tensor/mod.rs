@@ -1,18 +1,22 @@
 //! Rust Bindings for Tensor type
 
+use core::any::type_name;
 use core::convert::{TryFrom, TryInto};
-use core::mem::size_of;
+use core::mem::{align_of, size_of};
+use core::ptr::NonNull;
 use core::slice;
 use ordered_float::NotNan;
 
 use crate::bindings;
+use crate::Error;
 
 mod info;
+mod view;
 pub use info::TensorInfo;
+pub use view::{TensorView, TensorViewMut};
 
 /// A TensorFlow Tensor
 #[repr(transparent)]
-#[derive(Default)]
 pub struct Tensor(bindings::TfLiteTensor);
 
 /// Tensor element data types supported
@@ -20,6 +24,7 @@ pub struct Tensor(bindings::TfLiteTensor);
 pub enum ElementType {
     Float32,
     UInt8,
+    Int8,
     Int32,
 }
 impl TryFrom<bindings::TfLiteType> for ElementType {
@@ -31,6 +36,7 @@ impl TryFrom<bindings::TfLiteType> for ElementType {
         match status {
             bindings::TfLiteType::kTfLiteFloat32 => Ok(Float32),
             bindings::TfLiteType::kTfLiteUInt8 => Ok(UInt8),
+            bindings::TfLiteType::kTfLiteInt8 => Ok(Int8),
             bindings::TfLiteType::kTfLiteInt32 => Ok(Int32),
             t => Err(t),
         }
@@ -40,12 +46,26 @@ impl TryFrom<bindings::TfLiteType> for ElementType {
 /// Marker trait for those intristic types we support
 pub trait ElemTypeOf {
     fn elem_type_of() -> ElementType;
+
+    /// Whether `tensor`, which has the element type of `Self`, holds values
+    /// that aren't valid for `Self`, so it can't be accessed as `Self`s.
+    #[doc(hidden)]
+    fn has_invalid_values(_tensor: &Tensor) -> bool {
+        false
+    }
 }
 
 impl ElemTypeOf for NotNan<f32> {
     fn elem_type_of() -> ElementType {
         ElementType::Float32
     }
+
+    // An invoke may produce NaNs
+    fn has_invalid_values(tensor: &Tensor) -> bool {
+        tensor
+            .try_as_data::<f32>()
+            .map_or(false, |data| data.iter().any(|value| value.is_nan()))
+    }
 }
 impl ElemTypeOf for f32 {
     fn elem_type_of() -> ElementType {
@@ -57,25 +77,80 @@ impl ElemTypeOf for u8 {
         ElementType::UInt8
     }
 }
+impl ElemTypeOf for i8 {
+    fn elem_type_of() -> ElementType {
+        ElementType::Int8
+    }
+}
 impl ElemTypeOf for i32 {
     fn elem_type_of() -> ElementType {
         ElementType::Int32
     }
 }
 
-/// Implement From raw types to Tensor
-impl From<*mut bindings::TfLiteTensor> for &Tensor {
-    fn from(t: *mut bindings::TfLiteTensor) -> Self {
-        unsafe { &*(t as *mut Tensor) }
+/// Marker trait for types that any bit pattern is a valid value of, which
+/// tensor data can be reinterpreted as with [`Tensor::cast`]
+pub trait Pod: Copy + private::Sealed {}
+
+mod private {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_pod {
+    ($($t:ty),*) => {
+        $(
+            impl private::Sealed for $t {}
+            impl Pod for $t {}
+        )*
+    };
+}
+impl_pod!(u8, i8, u16, i16, u32, i32, u64, i64, f32, f64);
+
+/// Affine quantization parameters of a tensor.
+///
+/// A quantized value `q` represents the real value `(q - zero_point) *
+/// scale`. A `scale` of zero indicates that the tensor is not quantized.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct QuantizationParams {
+    pub scale: f32,
+    pub zero_point: i32,
+}
+impl From<bindings::TfLiteQuantizationParams> for QuantizationParams {
+    fn from(params: bindings::TfLiteQuantizationParams) -> Self {
+        Self {
+            scale: params.scale,
+            zero_point: params.zero_point,
+        }
     }
 }
-impl From<*mut bindings::TfLiteTensor> for &mut Tensor {
-    fn from(t: *mut bindings::TfLiteTensor) -> Self {
-        unsafe { &mut *(t as *mut Tensor) }
+impl QuantizationParams {
+    /// Returns whether these parameters describe a quantized tensor
+    pub fn is_quantized(&self) -> bool {
+        self.scale != 0.0
     }
 }
 
 impl Tensor {
+    /// Wrap a tensor owned by TensorFlow.
+    ///
+    /// # Safety
+    ///
+    /// `tensor` must be non-null and point to a valid `TfLiteTensor` that
+    /// isn't mutated for the lifetime `'a`.
+    pub(crate) unsafe fn from_raw<'a>(tensor: *const bindings::TfLiteTensor) -> &'a Self {
+        &*(tensor as *const Self)
+    }
+
+    /// Wrap a tensor owned by TensorFlow, mutably.
+    ///
+    /// # Safety
+    ///
+    /// `tensor` must be non-null and point to a valid `TfLiteTensor` that
+    /// isn't otherwise accessed for the lifetime `'a`.
+    pub(crate) unsafe fn from_raw_mut<'a>(tensor: *mut bindings::TfLiteTensor) -> &'a mut Self {
+        &mut *(tensor as *mut Self)
+    }
+
     /// The element type of this tensor.
     ///
     /// Returns `Some(element_type)` if the element type annotated on this
@@ -95,6 +170,17 @@ impl Tensor {
         self.inner().try_into().unwrap()
     }
 
+    /// Returns the [`QuantizationParams`](crate::tensor::QuantizationParams)
+    /// of this tensor
+    pub fn quantization_params(&self) -> QuantizationParams {
+        self.0.params.into()
+    }
+
+    /// The shape of this tensor
+    pub fn dims(&self) -> &[i32] {
+        info::dims(&self.0)
+    }
+
     pub(crate) fn inner(&self) -> &bindings::TfLiteTensor {
         &self.0
     }
@@ -108,24 +194,17 @@ impl Tensor {
     ///
     /// This method will panic if `T` does not match the data type
     /// annotated on this tensor. Call
-    /// [element_type()](#method.element_type) to discover the data type.
+    /// [element_type()](#method.element_type) to discover the data type,
+    /// or use [try_as_data()](#method.try_as_data) instead.
+    ///
+    /// Also panics if TensorFlow gave the tensor a null or misaligned data
+    /// pointer, or if the data holds values that aren't valid for `T`.
     pub fn as_data<T>(&self) -> &[T]
     where
         T: ElemTypeOf,
     {
-        assert!(
-            self.element_type().unwrap() == T::elem_type_of(),
-            "Type `{:?}` does not match the original type `{:?}`",
-            T::elem_type_of(),
-            self.0.type_
-        );
-
-        unsafe {
-            slice::from_raw_parts(
-                self.0.data.raw_const as *const T,
-                self.0.bytes / size_of::<T>(),
-            )
-        }
+        self.try_as_data()
+            .unwrap_or_else(|e| self.data_error::<T>(e))
     }
 
     /// Extracts the tensor's data as a mutable flat slice.
@@ -137,20 +216,412 @@ impl Tensor {
     ///
     /// This method will panic if `T` does not match the data type
     /// annotated on this tensor. Call
-    /// [element_type()](#method.element_type) to discover the data type.
+    /// [element_type()](#method.element_type) to discover the data type,
+    /// or use [try_as_data_mut()](#method.try_as_data_mut) instead.
+    ///
+    /// Also panics if TensorFlow gave the tensor a null or misaligned data
+    /// pointer, or if the data holds values that aren't valid for `T`.
     pub fn as_data_mut<T>(&mut self) -> &mut [T]
     where
         T: ElemTypeOf,
     {
-        assert!(
-            self.element_type().unwrap() == T::elem_type_of(),
-            "Type `{:?}` does not match the original type `{:?}`",
-            T::elem_type_of(),
-            self.0.type_
+        let (data, len) = self
+            .typed_data_parts::<T>()
+            .unwrap_or_else(|e| self.data_error::<T>(e));
+
+        unsafe { slice::from_raw_parts_mut(data, len) }
+    }
+
+    /// Extracts the tensor's data as a flat slice.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ElementTypeMismatch` if `T` does not match the data
+    /// type annotated on this tensor, including when that type isn't
+    /// supported by this crate.
+    ///
+    /// Returns `Error::NullTensorData` or `Error::MisalignedTensorData` if
+    /// TensorFlow gave the tensor an invalid data pointer.
+    ///
+    /// Returns `Error::InvalidTensorData` if the data holds values that
+    /// aren't valid for `T`, such as a NaN when `T` is `NotNan<f32>`.
+    pub fn try_as_data<T>(&self) -> Result<&[T], Error>
+    where
+        T: ElemTypeOf,
+    {
+        let (data, len) = self.typed_data_parts::<T>()?;
+
+        Ok(unsafe { slice::from_raw_parts(data, len) })
+    }
+
+    /// Extracts the tensor's data as a mutable flat slice.
+    ///
+    /// # Errors
+    ///
+    /// As for [try_as_data()](#method.try_as_data).
+    pub fn try_as_data_mut<T>(&mut self) -> Result<&mut [T], Error>
+    where
+        T: ElemTypeOf,
+    {
+        let (data, len) = self.typed_data_parts::<T>()?;
+
+        Ok(unsafe { slice::from_raw_parts_mut(data, len) })
+    }
+
+    /// A multi-dimensional view of the tensor's data.
+    ///
+    /// # Errors
+    ///
+    /// As for [try_as_data()](#method.try_as_data). Also returns
+    /// `Error::TensorDataLenMismatch` if the data doesn't match the
+    /// tensor's shape.
+    pub fn view<T>(&self) -> Result<TensorView<'_, T>, Error>
+    where
+        T: ElemTypeOf,
+    {
+        TensorView::new(self.try_as_data()?, self.dims())
+    }
+
+    /// A mutable multi-dimensional view of the tensor's data.
+    ///
+    /// # Errors
+    ///
+    /// As for [view()](#method.view).
+    pub fn view_mut<T>(&mut self) -> Result<TensorViewMut<'_, T>, Error>
+    where
+        T: ElemTypeOf,
+    {
+        let (data, len) = self.typed_data_parts::<T>()?;
+        let data = unsafe { slice::from_raw_parts_mut(data, len) };
+
+        TensorViewMut::new(data, self.dims())
+    }
+
+    /// The tensor's data as raw bytes, whatever its element type. For
+    /// example, to log a tensor of a type this crate doesn't support.
+    ///
+    /// # Panics
+    ///
+    /// Panics if TensorFlow gave the tensor a null data pointer.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.cast::<u8>()
+            .unwrap_or_else(|e| self.data_error::<u8>(e))
+    }
+
+    /// The tensor's data as mutable raw bytes, whatever its element type.
+    /// For example, to copy quantized input directly into the tensor.
+    ///
+    /// # Panics
+    ///
+    /// Panics if TensorFlow gave the tensor a null data pointer.
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        let (data, len) = self
+            .data_parts::<u8>()
+            .unwrap_or_else(|e| self.data_error::<u8>(e));
+
+        unsafe { slice::from_raw_parts_mut(data, len) }
+    }
+
+    /// Reinterprets the tensor's data as a slice of `U`, whatever its
+    /// element type. For example, an `Int8` tensor as `u8`s.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::TensorDataLenMismatch` if the data isn't a whole
+    /// number of `U`s, and `Error::MisalignedTensorData` if it isn't
+    /// aligned for `U`.
+    ///
+    /// Returns `Error::NullTensorData` if TensorFlow gave the tensor a null
+    /// data pointer.
+    pub fn cast<U>(&self) -> Result<&[U], Error>
+    where
+        U: Pod,
+    {
+        let (data, len) = self.data_parts::<U>()?;
+
+        Ok(unsafe { slice::from_raw_parts(data, len) })
+    }
+
+    /// Reinterprets the tensor's data as a mutable slice of `U`, whatever
+    /// its element type.
+    ///
+    /// # Errors
+    ///
+    /// As for [cast()](#method.cast).
+    pub fn cast_mut<U>(&mut self) -> Result<&mut [U], Error>
+    where
+        U: Pod,
+    {
+        let (data, len) = self.data_parts::<U>()?;
+
+        Ok(unsafe { slice::from_raw_parts_mut(data, len) })
+    }
+
+    /// As [data_parts()](#method.data_parts), but `T` must match the data
+    /// type annotated on this tensor.
+    fn typed_data_parts<T>(&self) -> Result<(*mut T, usize), Error>
+    where
+        T: ElemTypeOf,
+    {
+        if self.element_type() != Some(T::elem_type_of()) {
+            return Err(Error::ElementTypeMismatch);
+        }
+        if T::has_invalid_values(self) {
+            return Err(Error::InvalidTensorData);
+        }
+
+        self.data_parts()
+    }
+
+    /// Returns a pointer to the tensor's data as `U`s, and the number of
+    /// `U`s. The pointer is valid for `slice::from_raw_parts`.
+    fn data_parts<U>(&self) -> Result<(*mut U, usize), Error> {
+        let bytes = self.0.bytes;
+        if bytes % size_of::<U>() != 0 {
+            return Err(Error::TensorDataLenMismatch);
+        }
+
+        // An empty tensor may not have a data pointer at all
+        if bytes == 0 {
+            return Ok((NonNull::dangling().as_ptr(), 0));
+        }
+
+        let data = unsafe { self.0.data.raw } as *mut U;
+        if data.is_null() {
+            return Err(Error::NullTensorData);
+        }
+        if data as usize % align_of::<U>() != 0 {
+            return Err(Error::MisalignedTensorData);
+        }
+
+        Ok((data, bytes / size_of::<U>()))
+    }
+
+    /// Panics with a description of why the tensor's data can't be
+    /// accessed as `U`s
+    fn data_error<U>(&self, error: Error) -> ! {
+        match error {
+            Error::ElementTypeMismatch => panic!(
+                "Type `{}` does not match the original type `{:?}`",
+                type_name::<U>(),
+                self.0.type_
+            ),
+            Error::NullTensorData => panic!("Tensor data is a nullptr"),
+            Error::MisalignedTensorData => {
+                panic!("Tensor data is not aligned for `{}`", type_name::<U>())
+            }
+            Error::InvalidTensorData => {
+                panic!(
+                    "Tensor data holds values that aren't valid for `{}`",
+                    type_name::<U>()
+                )
+            }
+            e => panic!(
+                "Tensor data can't be accessed as `{}`: {:?}",
+                type_name::<U>(),
+                e
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    //! These tests don't call into TensorFlow, so they can be run with
+    //! Miri: `cargo miri test tensor::`
+    use super::*;
+
+    /// A `TfLiteTensor` owning its data, as TensorFlow would lay it out
+    /// in the arena.
+    pub(crate) struct MockTensor<D> {
+        pub(crate) raw: bindings::TfLiteTensor,
+        pub(crate) data: D,
+    }
+
+    impl<const N: usize> MockTensor<[f32; N]> {
+        pub(crate) fn new(data: [f32; N]) -> Self {
+            let mut raw = bindings::TfLiteTensor::default();
+            raw.type_ = bindings::TfLiteType::kTfLiteFloat32;
+            raw.bytes = N * size_of::<f32>();
+
+            Self { raw, data }
+        }
+
+        /// Point the raw tensor at its data. Must be called once the mock
+        /// is in its final place.
+        pub(crate) fn as_raw(&mut self) -> *mut bindings::TfLiteTensor {
+            self.raw.data.raw = self.data.as_mut_ptr().cast();
+            &mut self.raw
+        }
+    }
+
+    #[test]
+    fn as_data() {
+        let mut mock = MockTensor::new([1.0, 2.0, 3.0]);
+        let tensor = unsafe { Tensor::from_raw(mock.as_raw()) };
+
+        assert_eq!(tensor.element_type(), Some(ElementType::Float32));
+        assert_eq!(tensor.as_data::<f32>(), &[1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn as_data_mut() {
+        let mut mock = MockTensor::new([1.0, 2.0, 3.0]);
+        let tensor = unsafe { Tensor::from_raw_mut(mock.as_raw()) };
+
+        tensor.as_data_mut::<f32>()[1] = 5.0;
+        assert_eq!(tensor.as_data::<f32>(), &[1.0, 5.0, 3.0]);
+
+        assert_eq!(mock.data, [1.0, 5.0, 3.0]);
+    }
+
+    #[test]
+    fn as_data_empty_null() {
+        let mut mock = MockTensor::new([]);
+        let raw = mock.as_raw();
+        unsafe { (*raw).data.raw = core::ptr::null_mut() };
+
+        let tensor = unsafe { Tensor::from_raw(raw) };
+        assert!(tensor.as_data::<f32>().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "nullptr")]
+    fn as_data_null() {
+        let mut mock = MockTensor::new([1.0]);
+        let raw = mock.as_raw();
+        unsafe { (*raw).data.raw = core::ptr::null_mut() };
+
+        let tensor = unsafe { Tensor::from_raw(raw) };
+        let _ = tensor.as_data::<f32>();
+    }
+
+    #[test]
+    #[should_panic(expected = "not aligned")]
+    fn as_data_misaligned() {
+        let mut mock = MockTensor::new([1.0, 2.0]);
+        let raw = mock.as_raw();
+        unsafe {
+            (*raw).data.raw = (*raw).data.raw.cast::<u8>().add(1).cast();
+            (*raw).bytes -= size_of::<f32>();
+        }
+
+        let tensor = unsafe { Tensor::from_raw(raw) };
+        let _ = tensor.as_data::<f32>();
+    }
+
+    #[test]
+    #[should_panic(expected = "does not match")]
+    fn as_data_wrong_type() {
+        let mut mock = MockTensor::new([1.0]);
+        let tensor = unsafe { Tensor::from_raw(mock.as_raw()) };
+
+        let _ = tensor.as_data::<i32>();
+    }
+
+    #[test]
+    fn try_as_data() {
+        let mut mock = MockTensor::new([1.0, 2.0]);
+        let tensor = unsafe { Tensor::from_raw_mut(mock.as_raw()) };
+
+        assert_eq!(tensor.try_as_data::<f32>(), Ok(&[1.0, 2.0][..]));
+        assert_eq!(tensor.try_as_data::<i8>(), Err(Error::ElementTypeMismatch));
+
+        tensor.try_as_data_mut::<f32>().unwrap()[0] = 3.0;
+        assert_eq!(
+            tensor.try_as_data_mut::<u8>(),
+            Err(Error::ElementTypeMismatch)
+        );
+        assert_eq!(mock.data, [3.0, 2.0]);
+    }
+
+    #[test]
+    fn try_as_data_not_nan() {
+        let mut mock = MockTensor::new([1.0, 2.0]);
+        let tensor = unsafe { Tensor::from_raw_mut(mock.as_raw()) };
+
+        let not_nan = tensor.try_as_data::<NotNan<f32>>().unwrap();
+        assert_eq!(
+            not_nan,
+            &[NotNan::new(1.0).unwrap(), NotNan::new(2.0).unwrap()]
         );
 
+        // As an invoke might produce
+        tensor.as_data_mut::<f32>()[1] = f32::NAN;
+        assert_eq!(
+            tensor.try_as_data::<NotNan<f32>>(),
+            Err(Error::InvalidTensorData)
+        );
+        assert!(tensor.try_as_data_mut::<NotNan<f32>>().is_err());
+        assert!(tensor.view::<NotNan<f32>>().is_err());
+    }
+
+    #[test]
+    fn try_as_data_unsupported_type() {
+        let mut mock = MockTensor::new([1.0]);
+        let raw = mock.as_raw();
+        unsafe { (*raw).type_ = bindings::TfLiteType::kTfLiteInt16 };
+
+        let tensor = unsafe { Tensor::from_raw(raw) };
+        assert_eq!(tensor.element_type(), None);
+        assert_eq!(tensor.try_as_data::<f32>(), Err(Error::ElementTypeMismatch));
+
+        // Still readable as bytes
+        assert_eq!(tensor.as_bytes(), &1.0f32.to_ne_bytes()[..]);
+    }
+
+    #[test]
+    fn try_as_data_null() {
+        let mut mock = MockTensor::new([1.0]);
+        let raw = mock.as_raw();
+        unsafe { (*raw).data.raw = core::ptr::null_mut() };
+
+        let tensor = unsafe { Tensor::from_raw(raw) };
+        assert_eq!(tensor.try_as_data::<f32>(), Err(Error::NullTensorData));
+        assert_eq!(tensor.cast::<u8>(), Err(Error::NullTensorData));
+    }
+
+    #[test]
+    fn as_bytes_mut() {
+        let mut mock = MockTensor::new([0.0, 0.0]);
+        let tensor = unsafe { Tensor::from_raw_mut(mock.as_raw()) };
+
+        // As when copying input from a peripheral
+        tensor.as_bytes_mut()[4..].copy_from_slice(&2.5f32.to_ne_bytes());
+        assert_eq!(tensor.as_bytes().len(), 8);
+
+        assert_eq!(mock.data, [0.0, 2.5]);
+    }
+
+    #[test]
+    fn cast() {
+        let mut mock = MockTensor::new([1.0, -2.0, 0.5]);
+        let tensor = unsafe { Tensor::from_raw_mut(mock.as_raw()) };
+
+        assert_eq!(
+            tensor.cast::<u32>(),
+            Ok(&[0x3f80_0000, 0xc000_0000, 0x3f00_0000][..])
+        );
+        assert_eq!(tensor.cast::<i8>().map(<[i8]>::len), Ok(12));
+
+        // 12 bytes aren't a whole number of `u64`s
+        assert_eq!(tensor.cast::<u64>(), Err(Error::TensorDataLenMismatch));
+
+        tensor.cast_mut::<u32>().unwrap()[2] = 0x4000_0000;
+        assert_eq!(mock.data, [1.0, -2.0, 2.0]);
+    }
+
+    #[test]
+    fn cast_misaligned() {
+        let mut mock = MockTensor::new([1.0, 2.0, 3.0]);
+        let raw = mock.as_raw();
         unsafe {
-            slice::from_raw_parts_mut(self.0.data.raw as *mut T, self.0.bytes / size_of::<T>())
+            (*raw).data.raw = (*raw).data.raw.cast::<u8>().add(2).cast();
+            (*raw).bytes = 8;
         }
+
+        let tensor = unsafe { Tensor::from_raw(raw) };
+        assert_eq!(tensor.cast::<u32>(), Err(Error::MisalignedTensorData));
+        assert_eq!(tensor.cast::<u16>().map(<[u16]>::len), Ok(4));
     }
 }
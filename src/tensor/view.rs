@@ -0,0 +1,418 @@
+//! Multi-dimensional views of a tensor's data
+//!
+//! TensorFlow stores tensors in row-major order, so the last dimension is
+//! contiguous. For example, an image of shape `[1, H, W, C]` is indexed by
+//! `[n, h, w, c]`, and each row is the `C` channels of one pixel.
+
+use core::mem;
+
+use crate::Error;
+
+/// A multi-dimensional view of a tensor's data
+#[derive(Clone, Copy, Debug)]
+pub struct TensorView<'a, T> {
+    data: &'a [T],
+    dims: &'a [i32],
+}
+
+/// A mutable multi-dimensional view of a tensor's data
+#[derive(Debug)]
+pub struct TensorViewMut<'a, T> {
+    data: &'a mut [T],
+    dims: &'a [i32],
+}
+
+/// Returns the number of elements in a tensor of shape `dims`, or `None`
+/// if a dimension is negative.
+fn element_count(dims: &[i32]) -> Option<usize> {
+    dims.iter()
+        .map(|&d| usize::try_from(d).ok())
+        .try_fold(1usize, |count, d| count.checked_mul(d?))
+}
+
+/// Returns the flat offset of `index` into a tensor of shape `dims`, or
+/// `None` if it is out of bounds.
+fn offset(dims: &[i32], index: &[usize]) -> Option<usize> {
+    if index.len() != dims.len() {
+        return None;
+    }
+
+    dims.iter().zip(index).try_fold(0, |offset, (&d, &i)| {
+        let d = d as usize;
+        (i < d).then(|| offset * d + i)
+    })
+}
+
+/// Returns the number of elements in each sub-view along the first axis
+fn stride(dims: &[i32]) -> usize {
+    dims.iter().skip(1).map(|&d| d as usize).product()
+}
+
+/// Returns the number of sub-views along the first axis. A view with no
+/// dimensions has none.
+fn outer_len(dims: &[i32]) -> usize {
+    dims.first().map_or(0, |&d| d as usize)
+}
+
+/// Returns the length of a row, the last axis. A view with no dimensions
+/// is a single row of one element.
+fn row_len(dims: &[i32]) -> usize {
+    dims.last().map_or(1, |&d| d as usize)
+}
+
+/// Returns the number of rows, which is the product of all but the last
+/// axis
+fn row_count(dims: &[i32]) -> usize {
+    dims.split_last()
+        .map_or(1, |(_, outer)| outer.iter().map(|&d| d as usize).product())
+}
+
+/// Splits `data` into `count` consecutive slices of `len` elements. Unlike
+/// `chunks`, the slices may be empty, for an axis of length zero.
+fn split<T>(data: &[T], count: usize, len: usize) -> impl Iterator<Item = &[T]> {
+    let mut rest = data;
+
+    (0..count).map(move |_| {
+        let (chunk, tail) = rest.split_at(len);
+        rest = tail;
+        chunk
+    })
+}
+
+/// Splits `data` mutably, as [`split`](split)
+fn split_mut<T>(data: &mut [T], count: usize, len: usize) -> impl Iterator<Item = &mut [T]> {
+    let mut rest = data;
+
+    (0..count).map(move |_| {
+        let (chunk, tail) = mem::take(&mut rest).split_at_mut(len);
+        rest = tail;
+        chunk
+    })
+}
+
+impl<'a, T> TensorView<'a, T> {
+    /// Create a view of `data` with the shape `dims`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::TensorDataLenMismatch` if the number of elements in
+    /// `data` doesn't match `dims`.
+    pub fn new(data: &'a [T], dims: &'a [i32]) -> Result<Self, Error> {
+        match element_count(dims) {
+            Some(count) if count == data.len() => Ok(Self { data, dims }),
+            _ => Err(Error::TensorDataLenMismatch),
+        }
+    }
+
+    /// The shape of the view
+    pub fn dims(&self) -> &'a [i32] {
+        self.dims
+    }
+
+    /// The number of dimensions of the view
+    pub fn ndim(&self) -> usize {
+        self.dims.len()
+    }
+
+    /// The view's data, as a flat slice in row-major order
+    pub fn as_slice(&self) -> &'a [T] {
+        self.data
+    }
+
+    /// Returns the element at `index`, or `None` if `index` doesn't have
+    /// one entry per dimension or is out of bounds.
+    pub fn get<const N: usize>(&self, index: [usize; N]) -> Option<&'a T> {
+        offset(self.dims, &index).map(|offset| &self.data[offset])
+    }
+
+    /// Returns the `i`th sub-view along the first (batch) axis, which has
+    /// one dimension fewer. Returns `None` if the view has no dimensions,
+    /// or `i` is out of bounds.
+    pub fn subview(&self, i: usize) -> Option<TensorView<'a, T>> {
+        let (&first, dims) = self.dims.split_first()?;
+        if i >= first as usize {
+            return None;
+        }
+
+        let stride = stride(self.dims);
+        Some(TensorView {
+            data: &self.data[i * stride..(i + 1) * stride],
+            dims,
+        })
+    }
+
+    /// Iterates over the sub-views along the first (batch) axis. A view
+    /// with no dimensions has none.
+    pub fn outer_iter(&self) -> impl Iterator<Item = TensorView<'a, T>> {
+        let dims = self.dims.get(1..).unwrap_or(&[]);
+
+        split(self.data, outer_len(self.dims), stride(self.dims))
+            .map(move |data| TensorView { data, dims })
+    }
+
+    /// Returns the `i`th row, counting in row-major order. A row runs along
+    /// the last axis, so is contiguous.
+    pub fn row(&self, i: usize) -> Option<&'a [T]> {
+        self.rows().nth(i)
+    }
+
+    /// Iterates over the rows, in row-major order
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> {
+        split(self.data, row_count(self.dims), row_len(self.dims))
+    }
+}
+
+impl<'a, T> TensorViewMut<'a, T> {
+    /// Create a mutable view of `data` with the shape `dims`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::TensorDataLenMismatch` if the number of elements in
+    /// `data` doesn't match `dims`.
+    pub fn new(data: &'a mut [T], dims: &'a [i32]) -> Result<Self, Error> {
+        match element_count(dims) {
+            Some(count) if count == data.len() => Ok(Self { data, dims }),
+            _ => Err(Error::TensorDataLenMismatch),
+        }
+    }
+
+    /// An immutable view of the same data
+    pub fn view(&self) -> TensorView<'_, T> {
+        TensorView {
+            data: &*self.data,
+            dims: self.dims,
+        }
+    }
+
+    /// The shape of the view
+    pub fn dims(&self) -> &'a [i32] {
+        self.dims
+    }
+
+    /// The number of dimensions of the view
+    pub fn ndim(&self) -> usize {
+        self.dims.len()
+    }
+
+    /// The view's data, as a flat slice in row-major order
+    pub fn as_slice(&self) -> &[T] {
+        &*self.data
+    }
+
+    /// The view's data, as a mutable flat slice in row-major order
+    pub fn as_slice_mut(&mut self) -> &mut [T] {
+        &mut *self.data
+    }
+
+    /// Returns the element at `index`. See
+    /// [`TensorView::get`](TensorView::get).
+    pub fn get<const N: usize>(&self, index: [usize; N]) -> Option<&T> {
+        offset(self.dims, &index).map(|offset| &self.data[offset])
+    }
+
+    /// Returns the element at `index` mutably. See
+    /// [`TensorView::get`](TensorView::get).
+    pub fn get_mut<const N: usize>(&mut self, index: [usize; N]) -> Option<&mut T> {
+        offset(self.dims, &index).map(move |offset| &mut self.data[offset])
+    }
+
+    /// Returns the `i`th sub-view along the first (batch) axis. See
+    /// [`TensorView::subview`](TensorView::subview).
+    pub fn subview_mut(&mut self, i: usize) -> Option<TensorViewMut<'_, T>> {
+        let (&first, dims) = self.dims.split_first()?;
+        if i >= first as usize {
+            return None;
+        }
+
+        let stride = stride(self.dims);
+        Some(TensorViewMut {
+            data: &mut self.data[i * stride..(i + 1) * stride],
+            dims,
+        })
+    }
+
+    /// Iterates mutably over the sub-views along the first (batch) axis
+    pub fn outer_iter_mut(&mut self) -> impl Iterator<Item = TensorViewMut<'_, T>> {
+        let dims = self.dims.get(1..).unwrap_or(&[]);
+
+        split_mut(self.data, outer_len(self.dims), stride(self.dims))
+            .map(move |data| TensorViewMut { data, dims })
+    }
+
+    /// Returns the `i`th row mutably. See [`TensorView::row`](TensorView::row).
+    pub fn row_mut(&mut self, i: usize) -> Option<&mut [T]> {
+        self.rows_mut().nth(i)
+    }
+
+    /// Iterates mutably over the rows, in row-major order
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        let (count, len) = (row_count(self.dims), row_len(self.dims));
+
+        split_mut(self.data, count, len)
+    }
+}
+
+#[cfg(feature = "ndarray")]
+impl<'a, T> TensorView<'a, T> {
+    /// Convert to an [`ndarray`](https://docs.rs/ndarray) view
+    pub fn as_array(&self) -> ndarray::ArrayViewD<'a, T> {
+        let shape: std::vec::Vec<usize> = self.dims.iter().map(|&d| d as usize).collect();
+
+        ndarray::ArrayViewD::from_shape(shape, self.data).expect("Shape was checked on creation")
+    }
+}
+
+#[cfg(feature = "ndarray")]
+impl<T> TensorViewMut<'_, T> {
+    /// Convert to a mutable [`ndarray`](https://docs.rs/ndarray) view
+    pub fn as_array_mut(&mut self) -> ndarray::ArrayViewMutD<'_, T> {
+        let shape: std::vec::Vec<usize> = self.dims.iter().map(|&d| d as usize).collect();
+
+        ndarray::ArrayViewMutD::from_shape(shape, &mut *self.data)
+            .expect("Shape was checked on creation")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIMS: [i32; 4] = [2, 2, 3, 2];
+
+    fn data() -> [i32; 24] {
+        core::array::from_fn(|i| i as i32)
+    }
+
+    #[test]
+    fn new() {
+        let data = data();
+
+        assert!(TensorView::new(&data[..], &DIMS).is_ok());
+        assert_eq!(
+            TensorView::new(&data[1..], &DIMS).err(),
+            Some(Error::TensorDataLenMismatch)
+        );
+        assert_eq!(
+            TensorView::new(&data[..], &[-24]).err(),
+            Some(Error::TensorDataLenMismatch)
+        );
+
+        // A scalar has no dimensions
+        let scalar = TensorView::new(&data[..1], &[]).unwrap();
+        assert_eq!(scalar.get([]), Some(&0));
+    }
+
+    #[test]
+    fn get() {
+        let data = data();
+        let view = TensorView::new(&data[..], &DIMS).unwrap();
+
+        assert_eq!(view.get([0, 0, 0, 0]), Some(&0));
+        assert_eq!(view.get([0, 0, 0, 1]), Some(&1));
+        assert_eq!(view.get([0, 0, 1, 0]), Some(&2));
+        assert_eq!(view.get([0, 1, 0, 0]), Some(&6));
+        assert_eq!(view.get([1, 1, 2, 1]), Some(&23));
+
+        // Out of bounds, or the wrong number of dimensions
+        assert_eq!(view.get([0, 0, 3, 0]), None);
+        assert_eq!(view.get([2, 0, 0, 0]), None);
+        assert_eq!(view.get([0, 0, 0]), None);
+    }
+
+    #[test]
+    fn subview() {
+        let data = data();
+        let view = TensorView::new(&data[..], &DIMS).unwrap();
+
+        let batch = view.subview(1).unwrap();
+        assert_eq!(batch.dims(), &DIMS[1..]);
+        assert_eq!(batch.get([0, 1, 0]), Some(&14));
+        assert!(view.subview(2).is_none());
+
+        assert!(view.outer_iter().map(|v| v.as_slice()[0]).eq([0, 12]));
+    }
+
+    #[test]
+    fn rows() {
+        let data = data();
+        let view = TensorView::new(&data[..], &DIMS).unwrap();
+
+        assert_eq!(view.rows().count(), 12);
+        assert_eq!(view.row(0), Some(&[0, 1][..]));
+        assert_eq!(view.row(5), Some(&[10, 11][..]));
+        assert_eq!(view.row(12), None);
+    }
+
+    #[test]
+    fn zero_length_axis() {
+        let data: [i32; 0] = [];
+        let view = TensorView::new(&data[..], &[3, 0]).unwrap();
+
+        // Three rows, each empty
+        assert_eq!(view.rows().count(), 3);
+        assert!(view.rows().all(|row| row.is_empty()));
+        assert_eq!(view.row(2), Some(&[][..]));
+        assert_eq!(view.row(3), None);
+
+        assert_eq!(view.outer_iter().count(), 3);
+        assert!(view.outer_iter().all(|v| v.dims() == [0]));
+
+        // Or no rows at all
+        let view = TensorView::new(&data[..], &[0, 3]).unwrap();
+        assert_eq!(view.rows().count(), 0);
+        assert_eq!(view.outer_iter().count(), 0);
+
+        let mut data: [i32; 0] = [];
+        let mut view = TensorViewMut::new(&mut data[..], &[3, 0]).unwrap();
+        assert_eq!(view.rows_mut().count(), 3);
+        assert_eq!(view.outer_iter_mut().count(), 3);
+    }
+
+    #[test]
+    fn scalar() {
+        let data = [7];
+        let view = TensorView::new(&data[..], &[]).unwrap();
+
+        // No axis to iterate over, consistent with `subview`
+        assert!(view.subview(0).is_none());
+        assert_eq!(view.outer_iter().count(), 0);
+
+        // But a single row of the one element
+        assert!(view.rows().eq([&[7][..]]));
+
+        let mut data = [7];
+        let mut view = TensorViewMut::new(&mut data[..], &[]).unwrap();
+        assert!(view.subview_mut(0).is_none());
+        assert_eq!(view.outer_iter_mut().count(), 0);
+        assert_eq!(view.row_mut(0), Some(&mut [7][..]));
+    }
+
+    #[test]
+    fn view_mut() {
+        let mut data = data();
+        let mut view = TensorViewMut::new(&mut data[..], &DIMS).unwrap();
+
+        *view.get_mut([1, 0, 0, 0]).unwrap() = -1;
+        view.subview_mut(0).unwrap().row_mut(0).unwrap()[1] = -2;
+        for mut batch in view.outer_iter_mut() {
+            batch.rows_mut().last().unwrap()[0] = -3;
+        }
+
+        assert_eq!(view.view().get([1, 0, 0, 0]), Some(&-1));
+        assert_eq!(data[..2], [0, -2]);
+        assert_eq!(data[12], -1);
+        assert_eq!(data[10], -3);
+        assert_eq!(data[22], -3);
+    }
+
+    #[test]
+    #[cfg(feature = "ndarray")]
+    fn as_array() {
+        let data = data();
+        let view = TensorView::new(&data[..], &DIMS).unwrap();
+
+        let array = view.as_array();
+        assert_eq!(array.shape(), [2, 2, 3, 2]);
+        assert_eq!(array[[1, 1, 2, 1]], 23);
+    }
+}
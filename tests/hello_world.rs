@@ -0,0 +1,49 @@
+//! hello_world example
+
+use tflite_micro::{MicroInterpreter, Model, MutableOpResolver};
+
+use log::info;
+
+#[test]
+fn hello_world() {
+    env_logger::init();
+    info!("---- Starting tensorflow micro example: hello_world");
+
+    let model = include_bytes!("../examples/models/hello_world.tflite");
+
+    // Map the model into a usable data structure. This doesn't involve
+    // any copying or parsing, it's a very lightweight operation.
+    let model = Model::from_buffer(&model[..]).unwrap();
+
+    // Create an area of memory to use for input, output, and
+    // intermediate arrays.
+    const TENSOR_ARENA_SIZE: usize = 4 * 1024;
+    let mut tensor_arena: [u8; TENSOR_ARENA_SIZE] = [0; TENSOR_ARENA_SIZE];
+
+    // Pull in all needed operation implementations
+    let micro_op_resolver = MutableOpResolver::empty().fully_connected();
+
+    // Build an interpreter to run the model with
+    let mut interpreter =
+        MicroInterpreter::new(&model, &micro_op_resolver, &mut tensor_arena[..]).unwrap();
+
+    // Check properties of the input sensor
+    assert_eq!([1, 1], interpreter.input_info(0).dims);
+
+    // The model approximates sin(x), with the same tolerance as the
+    // upstream hello_world test
+    for x in [0.0f32, 1.0, 3.0, 5.0] {
+        interpreter.input(0, &[x]).unwrap();
+        interpreter.invoke().unwrap();
+
+        let output_tensor = interpreter.output(0);
+        assert_eq!([1, 1], output_tensor.info().dims);
+
+        let y = output_tensor.as_data::<f32>()[0];
+        info!("sin({}) = {}", x, y);
+
+        assert!((y - x.sin()).abs() < 0.05);
+    }
+
+    info!("---- Done");
+}
@@ -37,7 +37,7 @@ fn magic_wand() {
         .softmax();
 
     let mut interpreter =
-        MicroInterpreter::new(&model, micro_op_resolver, &mut tensor_arena[..]).unwrap();
+        MicroInterpreter::new(&model, &micro_op_resolver, &mut tensor_arena[..]).unwrap();
 
     // Four indices:
     // WingScore
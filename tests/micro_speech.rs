@@ -32,7 +32,7 @@ fn micro_speech() {
 
     // Build an interpreter to run the model with
     let mut interpreter =
-        MicroInterpreter::new(&model, micro_op_resolver, &mut tensor_arena[..]).unwrap();
+        MicroInterpreter::new(&model, &micro_op_resolver, &mut tensor_arena[..]).unwrap();
 
     // Check properties of the input sensor
     assert_eq!([1, 49, 40, 1], interpreter.input_info(0).dims);
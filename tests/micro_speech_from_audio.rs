@@ -1,27 +1,23 @@
 //! micro_speech example, from audio files
 
-use tflite_micro::{Frontend, MicroInterpreter, Model, MutableOpResolver};
+use tflite_micro::{FeatureScaling, Frontend, MicroInterpreter, Model, MutableOpResolver};
 
 use itertools::Itertools;
 use log::info;
 
 /// Returns 40 elements of micro_feature from an audio slice
 fn micro_speech_frontend(frontend: &mut Frontend, audio_slice: &[i16]) -> [u8; 40] {
-    // Run generate_micro_features
-    let mut output: [u16; 40] = [0; 40];
-    frontend.generate_micro_features(audio_slice, &mut output);
-
-    // Scaling, values derived from those used in the training pipeline. See
-    // https://github.com/tensorflow/tensorflow/blob/master/tensorflow/lite/micro/examples/micro_speech/micro_features/micro_features_generator.cc
+    // Run generate_features, scaling with the values used in the training
+    // pipeline
     let mut scaled_features: [u8; 40] = [0; 40];
-    for m in 0..40 {
-        let scaled = ((output[m] as f32 * 2550.) + 3328.) / 6656.;
-        scaled_features[m] = match scaled {
-            x if x < 0. => 0u8,
-            x if x > 255. => 255u8,
-            x => x as u8,
-        };
-    }
+    let status = frontend
+        .generate_features(
+            audio_slice,
+            &mut scaled_features,
+            FeatureScaling::MicroSpeech,
+        )
+        .unwrap();
+    assert!(status.produced);
 
     scaled_features
 }
@@ -61,7 +57,7 @@ fn micro_speech_with_audio() {
 
     // Build an interpreter to run the model with
     let mut interpreter =
-        MicroInterpreter::new(&model, micro_op_resolver, &mut tensor_arena[..]).unwrap();
+        MicroInterpreter::new(&model, &micro_op_resolver, &mut tensor_arena[..]).unwrap();
 
     // Check properties of the input sensor
     assert_eq!([1, 49, 40, 1], interpreter.input_info(0).dims);
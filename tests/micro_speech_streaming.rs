@@ -0,0 +1,139 @@
+//! micro_speech example, streaming audio through a `FeatureWindow`
+
+use tflite_micro::{
+    FeatureScaling, FeatureWindow, Frontend, FrontendConfig, MicroInterpreter, Model,
+    MutableOpResolver,
+};
+
+use itertools::Itertools;
+use log::info;
+
+#[test]
+fn micro_speech_streaming() {
+    env_logger::init();
+    info!("---- Starting tensorflow micro example: micro_speech_streaming");
+
+    let model = include_bytes!("../examples/models/micro_speech.tflite");
+    let yes_1000ms = &include_bytes!("../examples/models/yes_1000ms_sample.data")
+        .chunks_exact(2)
+        .map(|c| i16::from_le_bytes([c[0], c[1]]))
+        .collect_vec();
+
+    let model = Model::from_buffer(&model[..]).unwrap();
+
+    const TENSOR_ARENA_SIZE: usize = 10 * 1024;
+    let mut tensor_arena: [u8; TENSOR_ARENA_SIZE] = [0; TENSOR_ARENA_SIZE];
+
+    let micro_op_resolver = MutableOpResolver::empty()
+        .depthwise_conv_2d()
+        .fully_connected()
+        .softmax();
+
+    let mut interpreter =
+        MicroInterpreter::new(&model, &micro_op_resolver, &mut tensor_arena[..]).unwrap();
+
+    // Rolling window of 49 slices of 40 features
+    let mut window: FeatureWindow<49, 40> = FeatureWindow::new(Frontend::new().unwrap()).unwrap();
+
+    // Push audio in odd-sized chunks, as it might arrive from a DMA
+    let produced: usize = yes_1000ms
+        .chunks(123)
+        .map(|chunk| window.push_samples(chunk))
+        .sum();
+
+    // One slice for the first 30ms, then one every 20ms
+    assert_eq!(produced, 49);
+    assert_eq!(window.new_slices(), 49);
+    assert!(window.is_full());
+
+    let mut micro_feature = [0u8; 49 * 40];
+    window.copy_to(&mut micro_feature, FeatureScaling::MicroSpeech);
+    window.reset_new_slices();
+    assert_eq!(window.new_slices(), 0);
+
+    // Invoke interpreter
+    interpreter.input(0, &micro_feature).unwrap();
+    interpreter.invoke().unwrap();
+
+    let output_tensor = interpreter.output(0);
+    info!("{:?}", output_tensor.as_data::<u8>());
+
+    // Result must be 'yes'
+    assert_eq!(Some(2), output_tensor.as_data::<u8>().iter().position_max());
+
+    info!("---- Done");
+}
+
+#[test]
+fn partial_window() {
+    let mut frontend = Frontend::new().unwrap();
+    let mut output = [0u16; 40];
+
+    // 10ms of audio does not complete a 30ms window
+    let status = frontend
+        .generate_micro_features(&[0; 160], &mut output)
+        .unwrap();
+    assert_eq!(status.num_samples_read, 160);
+    assert!(!status.produced);
+
+    // The remaining 20ms does
+    let status = frontend
+        .generate_micro_features(&[0; 480], &mut output)
+        .unwrap();
+    assert_eq!(status.num_samples_read, 320);
+    assert!(status.produced);
+
+    // A long buffer yields every complete window, one per 20ms of audio
+    let produced = frontend.for_each_slice(&[0; 16_000], |slice| {
+        assert_eq!(slice.len(), 40);
+    });
+    assert_eq!(produced, 50);
+}
+
+#[test]
+fn reset_and_buffer_backed_frontend() {
+    static mut FRONTEND_BUFFER: [u8; 16 * 1024] = [0; 16 * 1024];
+
+    let buffer = unsafe { &mut *core::ptr::addr_of_mut!(FRONTEND_BUFFER) };
+    let mut frontend = Frontend::with_buffer(FrontendConfig::default(), buffer).unwrap();
+
+    let used = frontend.buffer_used_bytes().unwrap();
+    assert!(used > 0 && used <= 16 * 1024);
+
+    let mut output = [0u16; 40];
+
+    // A partially filled window is discarded by a reset
+    let status = frontend
+        .generate_micro_features(&[0; 160], &mut output)
+        .unwrap();
+    assert!(!status.produced);
+
+    frontend.reset();
+
+    let status = frontend
+        .generate_micro_features(&[0; 480], &mut output)
+        .unwrap();
+    assert_eq!(status.num_samples_read, 480);
+    assert!(status.produced);
+
+    // Heap-backed frontends report no buffer usage
+    let clone = frontend.try_clone().unwrap();
+    assert_eq!(clone.buffer_used_bytes(), None);
+
+    // Dropping a heap-backed frontend frees its state, and leaves the
+    // buffer-backed one intact
+    drop(clone);
+    frontend.reset();
+    let status = frontend
+        .generate_micro_features(&[0; 480], &mut output)
+        .unwrap();
+    assert!(status.produced);
+
+    // The buffer is returned for reuse once the frontend is done with
+    let buffer = frontend.into_buffer().unwrap();
+    assert_eq!(buffer.len(), 16 * 1024);
+    let frontend = Frontend::with_buffer(FrontendConfig::default(), buffer).unwrap();
+    assert_eq!(frontend.buffer_used_bytes(), Some(used));
+
+    assert!(Frontend::new().unwrap().into_buffer().is_none());
+}
@@ -31,7 +31,7 @@ fn person_detection() {
 
     // Build an interpreter to run the model with
     let mut interpreter =
-        MicroInterpreter::new(&model, micro_op_resolver, &mut tensor_arena[..]).unwrap();
+        MicroInterpreter::new(&model, &micro_op_resolver, &mut tensor_arena[..]).unwrap();
 
     // Check properties of the input sensor
     interpreter.input(0, person).unwrap();